@@ -0,0 +1,12 @@
+-- Add down migration script here
+DROP TRIGGER ensure_single_open_novel;
+
+CREATE TRIGGER ensure_single_open_novel
+BEFORE UPDATE ON novel
+FOR EACH ROW
+BEGIN
+    UPDATE novel SET is_open = 0 WHERE id != NEW.id;
+END;
+
+DROP TABLE chapter_rule;
+ALTER TABLE novel DROP COLUMN chapter_rule_presets;
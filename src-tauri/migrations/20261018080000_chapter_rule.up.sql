@@ -0,0 +1,21 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN chapter_rule_presets TEXT;
+
+CREATE TABLE chapter_rule (
+    id INTEGER PRIMARY KEY,
+    novel_id INTEGER,
+    name TEXT,
+    pattern TEXT,
+    created_at TEXT DEFAULT (DATETIME('now', 'localtime'))
+);
+
+-- 仅在打开小说时关闭其他小说，避免更新其他字段时误关闭正在阅读的小说
+DROP TRIGGER ensure_single_open_novel;
+
+CREATE TRIGGER ensure_single_open_novel
+BEFORE UPDATE OF is_open ON novel
+FOR EACH ROW
+WHEN NEW.is_open = 1
+BEGIN
+    UPDATE novel SET is_open = 0 WHERE id != NEW.id;
+END;
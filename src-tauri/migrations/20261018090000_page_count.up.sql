@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN page_count INTEGER;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE novel DROP COLUMN read_offset;
+ALTER TABLE novel DROP COLUMN read_paragraph;
+ALTER TABLE novel DROP COLUMN read_chapter;
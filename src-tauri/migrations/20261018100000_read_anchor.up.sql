@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN read_chapter INTEGER;
+ALTER TABLE novel ADD COLUMN read_paragraph INTEGER;
+ALTER TABLE novel ADD COLUMN read_offset INTEGER;
@@ -0,0 +1,12 @@
+-- Add up migration script here
+CREATE TABLE replace_rule (
+    id INTEGER PRIMARY KEY,
+    -- 为空时对所有小说生效
+    novel_id INTEGER,
+    name TEXT,
+    kind TEXT,
+    pattern TEXT,
+    replacement TEXT DEFAULT '',
+    enabled INTEGER DEFAULT 1,
+    created_at TEXT DEFAULT (DATETIME('now', 'localtime'))
+);
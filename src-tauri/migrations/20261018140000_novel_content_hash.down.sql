@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX idx_novel_content_hash;
+
+ALTER TABLE novel DROP COLUMN content_hash;
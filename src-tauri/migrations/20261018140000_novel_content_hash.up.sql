@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN content_hash TEXT;
+
+CREATE INDEX idx_novel_content_hash ON novel (content_hash);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX idx_novel_source_path;
+
+ALTER TABLE novel DROP COLUMN source_missing;
+
+ALTER TABLE novel DROP COLUMN source_path;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN source_path TEXT;
+
+ALTER TABLE novel ADD COLUMN source_missing INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_novel_source_path ON novel (source_path);
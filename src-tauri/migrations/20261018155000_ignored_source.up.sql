@@ -0,0 +1,6 @@
+-- Add up migration script here
+-- 监视的文件夹中已从书库删除的小说源文件，扫描时不再自动导入
+CREATE TABLE ignored_source (
+    path TEXT PRIMARY KEY,
+    created_at TEXT DEFAULT (DATETIME('now', 'localtime'))
+);
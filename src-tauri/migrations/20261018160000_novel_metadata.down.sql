@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE novel DROP COLUMN language;
+
+ALTER TABLE novel DROP COLUMN series;
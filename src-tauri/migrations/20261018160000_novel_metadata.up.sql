@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE novel ADD COLUMN series TEXT;
+
+ALTER TABLE novel ADD COLUMN language TEXT;
@@ -0,0 +1,136 @@
+use crate::{
+    db::{model::ChapterRule, Db},
+    store::{get_from_app_store, model::AppStoreKey},
+    utils::{
+        library,
+        reader::{
+            chapter::{
+                resolve_chapter_patterns, ChapterMatcher, ChapterPreview, ChapterRulePreset,
+            },
+            NovelReader, ReaderOptions,
+        },
+        sql,
+    },
+};
+
+#[tauri::command]
+pub fn get_chapter_rule_presets() -> Result<Vec<ChapterRulePreset>, String> {
+    Ok(ChapterRulePreset::all())
+}
+
+#[tauri::command]
+pub async fn get_chapter_rules(
+    db: tauri::State<'_, Db>,
+    novel_id: i64,
+) -> Result<Vec<ChapterRule>, String> {
+    let rules = sql::get_chapter_rules(&db, novel_id).await?;
+
+    Ok(rules)
+}
+
+#[tauri::command]
+pub async fn set_chapter_rule_presets(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    novel_id: i64,
+    presets: Vec<String>,
+) -> Result<(), String> {
+    if let Some(key) = presets
+        .iter()
+        .find(|key| ChapterRulePreset::find(key).is_none())
+    {
+        return Err(format!("章节规则预设不存在: {key}"));
+    }
+
+    sql::set_chapter_rule_presets(&db, novel_id, Some(presets.join(","))).await?;
+
+    library::reload_novel_reader(&app_handle, &db, novel_id).await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_chapter_rule(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    novel_id: i64,
+    name: String,
+    pattern: String,
+) -> Result<(), String> {
+    // 校验正则是否合法
+    ChapterMatcher::new(std::slice::from_ref(&pattern))?;
+
+    sql::add_chapter_rule(&db, novel_id, &name, &pattern).await?;
+
+    library::reload_novel_reader(&app_handle, &db, novel_id).await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_chapter_rule(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+) -> Result<(), String> {
+    let rule = sql::get_chapter_rule_by_id(&db, id).await?;
+
+    sql::delete_chapter_rule(&db, id).await?;
+
+    library::reload_novel_reader(&app_handle, &db, rule.novel_id).await?;
+
+    Ok(())
+}
+
+// 使用候选规则解析文件（或已导入的小说），预览章节划分结果，不保存任何内容
+#[tauri::command]
+pub async fn preview_chapters(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    path: Option<String>,
+    novel_id: Option<i64>,
+    presets: Vec<String>,
+    patterns: Vec<String>,
+) -> Result<Vec<ChapterPreview>, String> {
+    // 预览已导入的小说时，使用为其指定的编码、简繁转换与替换规则
+    let (path, encoding, conversion, cleanup_rules) = match (path, novel_id) {
+        (Some(path), _) => (
+            path,
+            None,
+            get_from_app_store(&app_handle, AppStoreKey::ChineseConversion).unwrap_or_default(),
+            library::get_cleanup_rules(&db, None).await?,
+        ),
+        (None, Some(novel_id)) => {
+            let novel = sql::get_novel_by_id(&db, novel_id).await?;
+            let conversion = library::resolve_chinese_conversion(&app_handle, &novel);
+            let cleanup_rules = library::get_cleanup_rules(&db, Some(novel_id)).await?;
+            (novel.path, novel.encoding, conversion, cleanup_rules)
+        }
+        (None, None) => return Err("请指定文件路径或小说".to_string()),
+    };
+
+    if let Some(key) = presets
+        .iter()
+        .find(|key| ChapterRulePreset::find(key).is_none())
+    {
+        return Err(format!("章节规则预设不存在: {key}"));
+    }
+
+    let line_size = get_from_app_store::<usize>(&app_handle, AppStoreKey::LineSize)
+        .ok_or_else(|| "读取每行字数设置失败".to_string())?;
+
+    let options = ReaderOptions {
+        line_size,
+        chapter_patterns: resolve_chapter_patterns(Some(&presets.join(",")), &patterns),
+        // 预览只需要章节标题，按每行字数排版即可
+        pixel_fit: None,
+        // 预览的规则不一定会被采用，不写入缓存
+        cache_dir: None,
+        encoding,
+        conversion,
+        cleanup_rules,
+    };
+
+    // 解析整个文件较慢，不能占用异步运行时
+    library::run_blocking(move || NovelReader::preview_chapters(&path, &options)).await
+}
@@ -1,4 +1,4 @@
-use std::sync::Mutex;
+use std::{path::Path, sync::Mutex};
 
 use serde_json::Value;
 use tauri::{Emitter, Manager};
@@ -8,10 +8,14 @@ use crate::{
     constants::event::*,
     db::Db,
     state::model::AppState,
-    store::{get_entries_from_app_store, model::AppStoreKey, reset_app_store, set_to_app_store},
+    store::{
+        get_entries_from_app_store, get_from_app_store, model::AppStoreKey, reset_app_store,
+        set_to_app_store,
+    },
     utils::{
-        reader::NovelReader,
-        shortcut, sql,
+        library,
+        reader::convert::ChineseConversion,
+        shortcut,
         update::{UpdateCheckResult, UpdateChecker},
     },
 };
@@ -45,6 +49,27 @@ pub fn set_check_update_interval(
     Ok(())
 }
 
+// 设置监视的文件夹，重新开始监视
+#[tauri::command]
+pub fn set_watched_folders(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    folders: Vec<String>,
+) -> Result<(), String> {
+    if let Some(folder) = folders.iter().find(|folder| !Path::new(folder).is_dir()) {
+        return Err(format!("文件夹不存在: {folder}"));
+    }
+
+    let mut state = state.lock().map_err(|e| e.to_string())?;
+
+    set_to_app_store(&app_handle, AppStoreKey::WatchedFolders, &folders)?;
+
+    state.folder_watcher.start(&app_handle, folders)?;
+
+    app_handle.emit(CONFIG_CHANGE, ()).unwrap();
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn check_update(app_handle: tauri::AppHandle) -> Result<(), String> {
     UpdateChecker::check_update(&app_handle).await;
@@ -136,56 +161,103 @@ pub fn set_transparent(app_handle: tauri::AppHandle, transparent: bool) -> Resul
 pub async fn set_line_size(
     app_handle: tauri::AppHandle,
     db: tauri::State<'_, Db>,
-    state: tauri::State<'_, Mutex<AppState>>,
     line_size: usize,
 ) -> Result<(), String> {
     set_to_app_store(&app_handle, AppStoreKey::LineSize, line_size)?;
 
-    let (novel_id, read_position, read_progress) = {
-        let mut state = state.lock().map_err(|e| e.to_string())?;
-        let Some(reader) = &mut state.novel_reader else {
-            return Err("暂无打开的小说".to_string());
-        };
+    library::relayout_novel_reader(&app_handle, &db).await?;
+
+    Ok(())
+}
 
-        let current_chapter = reader.current_chapter();
+#[tauri::command]
+pub async fn set_fit_to_window(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    fit_to_window: bool,
+) -> Result<(), String> {
+    set_to_app_store(&app_handle, AppStoreKey::FitToWindow, fit_to_window)?;
+    app_handle.emit(CONFIG_CHANGE, ()).unwrap();
 
-        let (lines, chapters) = NovelReader::read_lines(&reader.novel_path, line_size)?;
+    library::relayout_novel_reader(&app_handle, &db).await?;
 
-        let new_read_position = chapters
-            .iter()
-            .find(|chapter| chapter.index == current_chapter.index)
-            .map(|chapter| chapter.start_line)
-            .expect("当前章节丢失");
+    Ok(())
+}
 
-        reader.lines = lines;
-        reader.chapters = chapters;
-        reader.read_position = new_read_position;
-        (
-            reader.novel_id,
-            reader.read_position as i64,
-            reader.read_progress(),
-        )
-    };
+#[tauri::command]
+pub async fn set_chinese_conversion(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    conversion: String,
+) -> Result<(), String> {
+    if ChineseConversion::from_key(&conversion).is_none() {
+        return Err(format!("不支持的简繁转换: {conversion}"));
+    }
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    set_to_app_store(&app_handle, AppStoreKey::ChineseConversion, conversion)?;
+    app_handle.emit(CONFIG_CHANGE, ()).unwrap();
 
-    app_handle.emit(READER_CHANGE, ()).unwrap();
+    library::relayout_novel_reader(&app_handle, &db).await?;
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn set_font_size(app_handle: tauri::AppHandle, font_size: i64) -> Result<(), String> {
-    set_to_app_store(&app_handle, AppStoreKey::FontSize, font_size)?;
+pub fn set_paragraph_navigation(
+    app_handle: tauri::AppHandle,
+    paragraph_navigation: bool,
+) -> Result<(), String> {
+    set_to_app_store(
+        &app_handle,
+        AppStoreKey::ParagraphNavigation,
+        paragraph_navigation,
+    )?;
     app_handle.emit(CONFIG_CHANGE, ()).unwrap();
+    // 按段落翻页时阅读器显示整个段落
+    app_handle.emit(READER_CHANGE, ()).unwrap();
     Ok(())
 }
 
+// 阅读器窗口上报文本区域的宽度
+#[tauri::command]
+pub async fn set_line_width(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    line_width: f64,
+) -> Result<(), String> {
+    let current = get_from_app_store::<f64>(&app_handle, AppStoreKey::LineWidth).unwrap();
+
+    if (current - line_width).abs() < 0.5 {
+        return Ok(());
+    }
+
+    set_to_app_store(&app_handle, AppStoreKey::LineWidth, line_width)?;
+
+    relayout_if_fit_to_window(&app_handle, &db).await
+}
+
 #[tauri::command]
-pub fn set_font_family(app_handle: tauri::AppHandle, font_family: String) -> Result<(), String> {
+pub async fn set_font_size(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    font_size: i64,
+) -> Result<(), String> {
+    set_to_app_store(&app_handle, AppStoreKey::FontSize, font_size)?;
+    app_handle.emit(CONFIG_CHANGE, ()).unwrap();
+
+    relayout_if_fit_to_window(&app_handle, &db).await
+}
+
+#[tauri::command]
+pub async fn set_font_family(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    font_family: String,
+) -> Result<(), String> {
     set_to_app_store(&app_handle, AppStoreKey::FontFamily, font_family)?;
     app_handle.emit(CONFIG_CHANGE, ()).unwrap();
-    Ok(())
+
+    relayout_if_fit_to_window(&app_handle, &db).await
 }
 
 #[tauri::command]
@@ -196,16 +268,37 @@ pub fn set_line_height(app_handle: tauri::AppHandle, line_height: f64) -> Result
 }
 
 #[tauri::command]
-pub fn set_letter_spacing(app_handle: tauri::AppHandle, letter_spacing: f64) -> Result<(), String> {
+pub async fn set_letter_spacing(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    letter_spacing: f64,
+) -> Result<(), String> {
     set_to_app_store(&app_handle, AppStoreKey::LetterSpacing, letter_spacing)?;
     app_handle.emit(CONFIG_CHANGE, ()).unwrap();
-    Ok(())
+
+    relayout_if_fit_to_window(&app_handle, &db).await
 }
 
 #[tauri::command]
-pub fn set_font_weight(app_handle: tauri::AppHandle, font_weight: i64) -> Result<(), String> {
+pub async fn set_font_weight(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    font_weight: i64,
+) -> Result<(), String> {
     set_to_app_store(&app_handle, AppStoreKey::FontWeight, font_weight)?;
     app_handle.emit(CONFIG_CHANGE, ()).unwrap();
+
+    relayout_if_fit_to_window(&app_handle, &db).await
+}
+
+// 按窗口宽度排版时，字体或窗口宽度变化后需要重新排版
+async fn relayout_if_fit_to_window(app_handle: &tauri::AppHandle, db: &Db) -> Result<(), String> {
+    let fit_to_window = get_from_app_store::<bool>(app_handle, AppStoreKey::FitToWindow).unwrap();
+
+    if fit_to_window {
+        library::relayout_novel_reader(app_handle, db).await?;
+    }
+
     Ok(())
 }
 
@@ -321,9 +414,12 @@ pub fn reset_config(
 
     shortcut::unregister_all_shortcuts(&app_handle)?;
 
-    let state = state.lock().map_err(|e| e.to_string())?;
+    let mut state = state.lock().map_err(|e| e.to_string())?;
     let reading_mode = state.reading_mode;
 
+    // 监视的文件夹已被清空
+    state.folder_watcher.stop();
+
     let shortcuts = if reading_mode {
         shortcut::AppShortcut::all_shortcuts()
     } else {
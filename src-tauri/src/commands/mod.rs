@@ -1,6 +1,8 @@
+pub mod chapter;
 pub mod common;
 pub mod config;
 pub mod novel;
 pub mod os;
 pub mod reader;
+pub mod replace;
 pub mod window;
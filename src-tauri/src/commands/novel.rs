@@ -1,110 +1,70 @@
-use std::{
-    fs,
-    fs::File,
-    io::{BufReader, Read},
-    path::Path,
-    sync::Mutex,
-};
+use std::{fs, path::Path, sync::Mutex};
 
-use charset_normalizer_rs::from_bytes;
-use epub::doc::EpubDoc;
-use tauri::{Emitter, Manager};
+use tauri::Emitter;
 
 use crate::{
     constants::event::*,
-    db::{model::Novel, Db},
+    db::{
+        model::{Novel, NovelMetadataUpdate},
+        Db,
+    },
     state::model::AppState,
-    store::{get_from_app_store, model::AppStoreKey},
-    utils::{reader::NovelReader, sql},
+    utils::{
+        cover,
+        import::{self, DuplicateAction, ImportResult},
+        library,
+        reader::{
+            convert::ChineseConversion,
+            document::Document,
+            txt::{self, EncodingCandidate},
+            NovelReader,
+        },
+        sql,
+    },
 };
 
+// 导入小说，与已有的小说内容相同时按 on_duplicate 处理，为空时返回重复的小说由用户选择
 #[tauri::command]
 pub async fn add_novel(
     app_handle: tauri::AppHandle,
     db: tauri::State<'_, Db>,
     path: &str,
-) -> Result<(), String> {
-    let filepath = Path::new(path);
-
-    // 校验文件是否存在
-    if !filepath.exists() {
-        return Err(format!("文件不存在: {path}"));
-    }
-
-    let filename = filepath
-        .file_name()
-        .and_then(|name| name.to_str())
-        .ok_or_else(|| format!("无法获取文件名: {path}"))?;
-
-    let extension = filepath
-        .extension()
-        .ok_or_else(|| format!("无法获取文件扩展名: {path}"))?;
-
-    // 校验文件后缀名为 .txt 或 .epub
-    if extension != "txt" && extension != "epub" {
-        return Err(format!("文件格式不支持: {filename}"));
-    }
-
-    if extension == "txt" {
-        // 检查文件编码格式是否受支持
-        let file = File::open(path).map_err(|e| e.to_string())?;
-        let mut buf_reader = BufReader::new(file);
-        let mut buffer = Vec::new();
-
-        buf_reader
-            .read_to_end(&mut buffer)
-            .map_err(|e| e.to_string())?;
-
-        let result = from_bytes(&buffer, None);
-        result.get_best().ok_or("文件编码不支持")?;
-    }
-
-    // 将文件复制到应用程序目录
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| e.to_string())?;
-
-    let new_path = app_data_dir.join(filename);
+    on_duplicate: Option<DuplicateAction>,
+) -> Result<ImportResult, String> {
+    import::import_novel(&app_handle, &db, path, on_duplicate).await
+}
 
-    fs::copy(path, &new_path).map_err(|e| e.to_string())?;
+// 在后台批量导入文件或文件夹中的小说，进度通过事件通知，on_duplicate 为空时重复的小说不导入
+#[tauri::command]
+pub fn import_novels(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    paths: Vec<String>,
+    recursive: bool,
+    on_duplicate: Option<DuplicateAction>,
+) -> Result<(), String> {
+    let mut state = state.lock().map_err(|e| e.to_string())?;
 
-    let title = match filename.split(".").next() {
-        Some(v) => v,
-        None => return Err(format!("非法文件名: {filename}")),
-    };
+    state
+        .import_task
+        .start(&app_handle, paths, recursive, on_duplicate)
+}
 
-    let (cover, author, description) = if extension == "epub" {
-        let mut doc = EpubDoc::new(&new_path).map_err(|e| e.to_string())?;
-        let cover = doc.get_cover().map(|(blob, _)| blob);
-        let author = doc.mdata("creator");
-        let description = doc.mdata("description");
-        (cover, author, description)
-    } else {
-        (None, None, None)
-    };
+#[tauri::command]
+pub fn cancel_import(state: tauri::State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
 
-    let new_path_str = new_path
-        .to_str()
-        .ok_or_else(|| format!("文件路径转换失败: {new_path:?}"))?;
+    state.import_task.cancel();
 
-    let file_size = filepath
-        .metadata()
-        .map(|metadata| metadata.len())
-        .map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-    sql::add_novel(
-        &db,
-        title,
-        cover,
-        author,
-        description,
-        new_path_str,
-        file_size as i64,
-    )
-    .await?;
+// 是否有正在进行的批量导入，用于重新打开窗口时恢复进度显示
+#[tauri::command]
+pub fn is_importing(state: tauri::State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(state.import_task.is_running())
 }
 
 #[tauri::command]
@@ -131,17 +91,14 @@ pub async fn open_novel(
     let novel = sql::get_novel_by_id(&db, id).await?;
 
     {
-        let line_size = get_from_app_store::<usize>(&app_handle, AppStoreKey::LineSize).unwrap();
-
         // 创建 reader 并更新状态
-        let reader = NovelReader::new(
-            novel.id,
-            novel.path,
-            novel.read_position as usize,
-            line_size,
-        )?;
-        let mut state = state.lock().map_err(|e| e.to_string())?;
-        state.novel_reader = Some(reader);
+        let reader = library::create_novel_reader(&app_handle, &db, novel).await?;
+        let (document, options) = (reader.document.clone(), reader.options.clone());
+        {
+            let mut state = state.lock().map_err(|e| e.to_string())?;
+            state.novel_reader = Some(reader);
+        }
+        document.remove_stale_cache(&options);
     }
 
     sql::open_novel(&db, id).await?;
@@ -174,19 +131,33 @@ pub async fn delete_novel(
 ) -> Result<(), String> {
     let novel = sql::get_novel_by_id(&db, id).await?;
 
-    let filepath = Path::new(&novel.path);
+    sql::delete_novel(&db, id).await?;
 
-    if filepath.exists() {
-        fs::remove_file(filepath).map_err(|e| e.to_string())?;
+    // 小说被删除后，监视的文件夹扫描到其源文件时不再自动导入
+    if let Some(source_path) = &novel.source_path {
+        sql::add_ignored_source(&db, source_path).await?;
     }
 
-    sql::delete_novel(&db, id).await?;
+    {
+        let mut state = state.lock().map_err(|e| e.to_string())?;
 
-    let mut state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(reader) = &state.novel_reader {
+            if reader.novel_id == id {
+                state.novel_reader = None;
+            }
+        }
+    }
+
+    // 旧版本导入的同名文件可能被多本小说共用，仍被使用时保留文件及其解析缓存
+    if sql::count_novels_by_path(&db, &novel.path).await? == 0 {
+        let filepath = Path::new(&novel.path);
 
-    if let Some(reader) = &state.novel_reader {
-        if reader.novel_id == id {
-            state.novel_reader = None;
+        if filepath.exists() {
+            fs::remove_file(filepath).map_err(|e| e.to_string())?;
+        }
+
+        if let Some(cache_dir) = library::document_cache_dir(&app_handle) {
+            Document::remove_cache(&cache_dir, &novel.path);
         }
     }
 
@@ -194,3 +165,134 @@ pub async fn delete_novel(
 
     Ok(())
 }
+
+// 列出小说文件可能使用的编码及示例文本，供自动检测出错时手动选择
+#[tauri::command]
+pub async fn get_encoding_candidates(
+    db: tauri::State<'_, Db>,
+    id: i64,
+) -> Result<Vec<EncodingCandidate>, String> {
+    let novel = sql::get_novel_by_id(&db, id).await?;
+
+    if !NovelReader::is_plain_text(&novel.path) {
+        return Err("该格式不支持指定编码".to_string());
+    }
+
+    txt::encoding_candidates(&novel.path)
+}
+
+// 编辑小说信息，除书名外为空时清除
+#[tauri::command]
+pub async fn update_novel_metadata(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+    metadata: NovelMetadataUpdate,
+) -> Result<(), String> {
+    let title = metadata.title.trim().to_string();
+
+    if title.is_empty() {
+        return Err("书名不能为空".to_string());
+    }
+
+    let novel = sql::get_novel_by_id(&db, id).await?;
+
+    let metadata = NovelMetadataUpdate {
+        title,
+        author: non_empty(metadata.author),
+        description: non_empty(metadata.description),
+        series: non_empty(metadata.series),
+        language: non_empty(metadata.language),
+    };
+
+    sql::update_novel_metadata(&db, id, metadata).await?;
+
+    // 正在阅读的小说信息变化时通知前端刷新
+    if novel.is_open != 0 {
+        app_handle.emit(READER_CHANGE, ()).unwrap();
+    }
+
+    Ok(())
+}
+
+// 使用图片文件作为小说封面，path 为空时清除封面
+#[tauri::command]
+pub async fn set_novel_cover(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+    path: Option<String>,
+) -> Result<(), String> {
+    let novel = sql::get_novel_by_id(&db, id).await?;
+
+    let cover = match path {
+        Some(path) => Some(cover::read_cover_file(Path::new(&path))?),
+        None => None,
+    };
+
+    sql::set_novel_cover(&db, id, cover).await?;
+
+    // 正在阅读的小说封面变化时通知前端刷新
+    if novel.is_open != 0 {
+        app_handle.emit(READER_CHANGE, ()).unwrap();
+    }
+
+    Ok(())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// 指定小说使用的编码，为空时恢复自动检测
+#[tauri::command]
+pub async fn set_novel_encoding(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+    encoding: Option<String>,
+) -> Result<(), String> {
+    let novel = sql::get_novel_by_id(&db, id).await?;
+
+    if !NovelReader::is_plain_text(&novel.path) {
+        return Err("该格式不支持指定编码".to_string());
+    }
+
+    if let Some(encoding) = encoding.as_deref() {
+        if !txt::is_known_encoding(encoding) {
+            return Err(format!("不支持的编码: {encoding}"));
+        }
+    }
+
+    sql::set_novel_encoding(&db, id, encoding).await?;
+
+    library::reload_novel_reader(&app_handle, &db, id).await?;
+
+    Ok(())
+}
+
+// 单独设置小说的简繁转换，为空时使用全局设置
+#[tauri::command]
+pub async fn set_novel_chinese_conversion(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+    conversion: Option<String>,
+) -> Result<(), String> {
+    // 确认小说存在，避免更新不存在的记录时静默成功
+    sql::get_novel_by_id(&db, id).await?;
+
+    if let Some(conversion) = conversion.as_deref() {
+        if ChineseConversion::from_key(conversion).is_none() {
+            return Err(format!("不支持的简繁转换: {conversion}"));
+        }
+    }
+
+    sql::set_novel_chinese_conversion(&db, id, conversion).await?;
+
+    library::reload_novel_reader(&app_handle, &db, id).await?;
+
+    Ok(())
+}
@@ -6,18 +6,22 @@ use crate::{
     constants::event::*,
     db::Db,
     state::model::AppState,
-    utils::{reader::NovelReader, sql},
+    store::{get_from_app_store, model::AppStoreKey},
+    utils::{
+        reader::{Line, NovelReaderInfo},
+        sql,
+    },
 };
 
 #[tauri::command]
 pub async fn get_novel_reader(
     state: tauri::State<'_, Mutex<AppState>>,
-) -> Result<NovelReader, String> {
+) -> Result<NovelReaderInfo, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
     let reader = &state.novel_reader;
 
     if let Some(reader) = reader {
-        Ok(reader.clone())
+        Ok(reader.info())
     } else {
         Err("暂无打开的小说".to_string())
     }
@@ -38,14 +42,24 @@ pub async fn close_novel_reader(
     Ok(())
 }
 
+// 返回当前行；按段落翻页时返回从当前行到段落结束的各行，已读完时为空
 #[tauri::command]
-pub async fn get_line(state: tauri::State<'_, Mutex<AppState>>) -> Result<String, String> {
+pub async fn get_line(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<Vec<Line>, String> {
+    let paragraph_navigation = is_paragraph_navigation(&app_handle);
+
     let state = state.lock().map_err(|e| e.to_string())?;
     let reader = &state.novel_reader;
 
     if let Some(reader) = reader {
-        let line = reader.get_line().unwrap_or("");
-        Ok(line.to_string())
+        let lines = if paragraph_navigation {
+            reader.paragraph_lines()
+        } else {
+            reader.get_line().into_iter().collect()
+        };
+        Ok(lines.into_iter().cloned().collect())
     } else {
         Err("暂无打开的小说".to_string())
     }
@@ -58,7 +72,7 @@ pub async fn set_read_position(
     state: tauri::State<'_, Mutex<AppState>>,
     read_position: usize,
 ) -> Result<(), String> {
-    let (novel_id, read_position, read_progress) = {
+    let (novel_id, read_position, read_progress, read_anchor) = {
         let mut state = state.lock().map_err(|e| e.to_string())?;
         let reader = &mut state.novel_reader;
 
@@ -71,13 +85,14 @@ pub async fn set_read_position(
                 reader.novel_id,
                 reader.read_position as i64,
                 reader.read_progress(),
+                reader.read_anchor(),
             )
         } else {
             return Err("暂无打开的小说".to_string());
         }
     };
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    sql::save_novel(&db, novel_id, read_position, read_progress, &read_anchor).await?;
 
     Ok(())
 }
@@ -88,12 +103,18 @@ pub async fn next_line(
     db: tauri::State<'_, Db>,
     state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let (novel_id, read_position, read_progress) = {
+    let paragraph_navigation = is_paragraph_navigation(&app_handle);
+
+    let (novel_id, read_position, read_progress, read_anchor) = {
         let mut state = state.lock().map_err(|e| e.to_string())?;
         let reader = &mut state.novel_reader;
 
         if let Some(reader) = reader {
-            reader.next_line()?;
+            if paragraph_navigation {
+                reader.next_paragraph()?;
+            } else {
+                reader.next_line()?;
+            }
             app_handle
                 .emit(READER_CHANGE, ())
                 .map_err(|e| e.to_string())?;
@@ -101,13 +122,14 @@ pub async fn next_line(
                 reader.novel_id,
                 reader.read_position as i64,
                 reader.read_progress(),
+                reader.read_anchor(),
             )
         } else {
             return Err("暂无打开的小说".to_string());
         }
     };
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    sql::save_novel(&db, novel_id, read_position, read_progress, &read_anchor).await?;
 
     Ok(())
 }
@@ -118,12 +140,18 @@ pub async fn prev_line(
     db: tauri::State<'_, Db>,
     state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let (novel_id, read_position, read_progress) = {
+    let paragraph_navigation = is_paragraph_navigation(&app_handle);
+
+    let (novel_id, read_position, read_progress, read_anchor) = {
         let mut state = state.lock().map_err(|e| e.to_string())?;
         let reader = &mut state.novel_reader;
 
         if let Some(reader) = reader {
-            reader.prev_line()?;
+            if paragraph_navigation {
+                reader.prev_paragraph()?;
+            } else {
+                reader.prev_line()?;
+            }
             app_handle
                 .emit(READER_CHANGE, ())
                 .map_err(|e| e.to_string())?;
@@ -131,13 +159,14 @@ pub async fn prev_line(
                 reader.novel_id,
                 reader.read_position as i64,
                 reader.read_progress(),
+                reader.read_anchor(),
             )
         } else {
             return Err("暂无打开的小说".to_string());
         }
     };
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    sql::save_novel(&db, novel_id, read_position, read_progress, &read_anchor).await?;
 
     Ok(())
 }
@@ -148,7 +177,7 @@ pub async fn next_chapter(
     db: tauri::State<'_, Db>,
     state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let (novel_id, read_position, read_progress) = {
+    let (novel_id, read_position, read_progress, read_anchor) = {
         let mut state = state.lock().map_err(|e| e.to_string())?;
         let reader = &mut state.novel_reader;
 
@@ -161,13 +190,14 @@ pub async fn next_chapter(
                 reader.novel_id,
                 reader.read_position as i64,
                 reader.read_progress(),
+                reader.read_anchor(),
             )
         } else {
             return Err("暂无打开的小说".to_string());
         }
     };
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    sql::save_novel(&db, novel_id, read_position, read_progress, &read_anchor).await?;
 
     Ok(())
 }
@@ -178,7 +208,7 @@ pub async fn prev_chapter(
     db: tauri::State<'_, Db>,
     state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let (novel_id, read_position, read_progress) = {
+    let (novel_id, read_position, read_progress, read_anchor) = {
         let mut state = state.lock().map_err(|e| e.to_string())?;
         let reader = &mut state.novel_reader;
 
@@ -191,13 +221,18 @@ pub async fn prev_chapter(
                 reader.novel_id,
                 reader.read_position as i64,
                 reader.read_progress(),
+                reader.read_anchor(),
             )
         } else {
             return Err("暂无打开的小说".to_string());
         }
     };
 
-    sql::save_novel(&db, novel_id, read_position, read_progress).await?;
+    sql::save_novel(&db, novel_id, read_position, read_progress, &read_anchor).await?;
 
     Ok(())
 }
+
+fn is_paragraph_navigation(app_handle: &tauri::AppHandle) -> bool {
+    get_from_app_store::<bool>(app_handle, AppStoreKey::ParagraphNavigation).unwrap_or_default()
+}
@@ -0,0 +1,116 @@
+use std::sync::Mutex;
+
+use crate::{
+    db::{model::ReplaceRule, Db},
+    state::model::AppState,
+    utils::{
+        library,
+        reader::cleanup::{Cleaner, CleanupPreview, CleanupRule, ReplaceRuleKind},
+        sql,
+    },
+};
+
+// 获取对小说生效的替换规则（含已停用的），novel_id 为空时只获取全局规则
+#[tauri::command]
+pub async fn get_replace_rules(
+    db: tauri::State<'_, Db>,
+    novel_id: Option<i64>,
+) -> Result<Vec<ReplaceRule>, String> {
+    let rules = sql::get_replace_rules(&db, novel_id).await?;
+
+    Ok(rules)
+}
+
+#[tauri::command]
+pub async fn add_replace_rule(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    novel_id: Option<i64>,
+    name: String,
+    kind: String,
+    pattern: String,
+    replacement: String,
+) -> Result<(), String> {
+    // 校验规则类型与正则是否合法
+    to_cleaner(&kind, &pattern, &replacement)?;
+
+    sql::add_replace_rule(&db, novel_id, &name, &kind, &pattern, &replacement).await?;
+
+    reload_affected_reader(&app_handle, &db, novel_id).await
+}
+
+#[tauri::command]
+pub async fn set_replace_rule_enabled(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+    enabled: bool,
+) -> Result<(), String> {
+    let rule = sql::get_replace_rule_by_id(&db, id).await?;
+
+    sql::set_replace_rule_enabled(&db, id, enabled).await?;
+
+    reload_affected_reader(&app_handle, &db, rule.novel_id).await
+}
+
+#[tauri::command]
+pub async fn delete_replace_rule(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    id: i64,
+) -> Result<(), String> {
+    let rule = sql::get_replace_rule_by_id(&db, id).await?;
+
+    sql::delete_replace_rule(&db, id).await?;
+
+    reload_affected_reader(&app_handle, &db, rule.novel_id).await
+}
+
+// 预览候选规则对当前章节的修改，只返回被修改或删除的段落，不保存任何内容
+// 当前章节已经执行过已有的规则，预览展示的是在此基础上新增该规则的效果
+#[tauri::command]
+pub async fn preview_replace_rule(
+    state: tauri::State<'_, Mutex<AppState>>,
+    kind: String,
+    pattern: String,
+    replacement: String,
+) -> Result<Vec<CleanupPreview>, String> {
+    let cleaner = to_cleaner(&kind, &pattern, &replacement)?;
+
+    let state = state.lock().map_err(|e| e.to_string())?;
+
+    let Some(reader) = &state.novel_reader else {
+        return Err("暂无打开的小说".to_string());
+    };
+
+    let paragraphs = reader.current_chapter_paragraphs()?;
+
+    Ok(cleaner.preview(&paragraphs))
+}
+
+fn to_cleaner(kind: &str, pattern: &str, replacement: &str) -> Result<Cleaner, String> {
+    let kind =
+        ReplaceRuleKind::from_key(kind).ok_or_else(|| format!("替换规则类型不存在: {kind}"))?;
+
+    if pattern.is_empty() {
+        return Err("替换规则的匹配内容不能为空".to_string());
+    }
+
+    Cleaner::new(&[CleanupRule {
+        kind,
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+    }])
+}
+
+// 全局规则影响正在阅读的小说，小说的规则只影响该小说
+async fn reload_affected_reader(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    novel_id: Option<i64>,
+) -> Result<(), String> {
+    match novel_id {
+        Some(novel_id) => library::reload_novel_reader(app_handle, db, novel_id).await,
+        None => library::relayout_novel_reader(app_handle, db).await,
+    }
+}
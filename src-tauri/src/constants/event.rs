@@ -15,3 +15,18 @@ pub const UPDATE_PROGRESS_CHANGE: &str = "update-progress-change";
 
 // 自动更新完成事件，用于通知前端自动更新完成
 pub const UPDATE_FINISHED: &str = "update-finished";
+
+// 批量导入进度变化事件，每开始导入一个文件时通知前端
+pub const IMPORT_PROGRESS_CHANGE: &str = "import-progress-change";
+
+// 批量导入中单个文件导入成功事件
+pub const IMPORT_SUCCEEDED: &str = "import-succeeded";
+
+// 批量导入中单个文件导入失败事件
+pub const IMPORT_FAILED: &str = "import-failed";
+
+// 批量导入结束事件（包括被取消）
+pub const IMPORT_FINISHED: &str = "import-finished";
+
+// 书库变化事件，监视文件夹自动导入小说或源文件状态变化时通知前端刷新
+pub const LIBRARY_CHANGE: &str = "library-change";
@@ -15,6 +15,9 @@ pub const DEFAULT_LANGUAGE: &str = "zh-CN";
 // 主题
 pub const DEFAULT_THEME: &str = "system";
 
+// 监视的文件夹，新出现的小说自动导入
+pub const DEFAULT_WATCHED_FOLDERS: &[&str] = &[];
+
 // 是否显示 Dock 图标
 pub const DEFAULT_DOCK_VISIBILITY: bool = true;
 
@@ -27,6 +30,18 @@ pub const DEFAULT_TRANSPARENT: bool = true;
 // 每页字数
 pub const DEFAULT_LINE_SIZE: u32 = 50;
 
+// 是否按阅读器窗口宽度排版
+pub const DEFAULT_FIT_TO_WINDOW: bool = false;
+
+// 简繁转换，可在小说中单独设置
+pub const DEFAULT_CHINESE_CONVERSION: &str = "none";
+
+// 是否按段落翻页，开启后上一行、下一行按整个段落移动
+pub const DEFAULT_PARAGRAPH_NAVIGATION: bool = false;
+
+// 阅读器窗口中文本区域的宽度，由阅读器窗口上报，0 表示尚未上报
+pub const DEFAULT_LINE_WIDTH: f64 = 0.0;
+
 // 字体大小
 pub const DEFAULT_FONT_SIZE: u32 = 16;
 
@@ -15,4 +15,65 @@ pub struct Novel {
     pub created_at: String,
     pub updated_at: String,
     pub is_open: i64,
+    pub chapter_rule_presets: Option<String>,
+    pub page_count: Option<i64>,
+    // 与排版无关的阅读位置锚点
+    pub read_chapter: Option<i64>,
+    pub read_paragraph: Option<i64>,
+    pub read_offset: Option<i64>,
+    // 手动指定的文本编码，为空时自动检测
+    pub encoding: Option<String>,
+    // 单独设置的简繁转换，为空时使用全局设置
+    pub chinese_conversion: Option<String>,
+    // 内容哈希，用于导入时检测重复的小说；纯文本按解码后的正文计算
+    pub content_hash: Option<String>,
+    // 导入时的源文件路径，用于监视文件夹
+    pub source_path: Option<String>,
+    // 源文件是否已被移除或重命名
+    pub source_missing: i64,
+    // 系列
+    pub series: Option<String>,
+    // 语言
+    pub language: Option<String>,
+}
+
+// 可编辑的小说信息，除书名外为空时清除
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NovelMetadataUpdate {
+    pub title: String,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub series: Option<String>,
+    pub language: Option<String>,
+}
+
+// 小说的源文件，监视文件夹时只需要这几个字段
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct NovelSource {
+    pub id: i64,
+    pub source_path: String,
+    pub source_missing: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ChapterRule {
+    pub id: i64,
+    pub novel_id: i64,
+    pub name: String,
+    pub pattern: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ReplaceRule {
+    pub id: i64,
+    // 为空时对所有小说生效
+    pub novel_id: Option<i64>,
+    pub name: String,
+    // literal、regex 或 delete_line
+    pub kind: String,
+    pub pattern: String,
+    pub replacement: String,
+    pub enabled: i64,
+    pub created_at: String,
 }
@@ -12,18 +12,26 @@ use tauri::{
     is_dev,
     menu::{MenuBuilder, MenuItemBuilder},
     tray::TrayIconBuilder,
-    Manager, RunEvent,
+    Emitter, Manager, RunEvent,
 };
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 use tauri_plugin_log::fern::colors::ColoredLevelConfig;
 
 use crate::{
-    commands::{common, config, novel, os, reader, window},
-    db::setup_db,
+    commands::{chapter, common, config, novel, os, reader, replace, window},
+    constants::event::READER_CHANGE,
+    db::{setup_db, Db},
     state::{model::AppState, toggle_reading_mode},
     store::{get_from_app_store, init_app_store, model::AppStoreKey},
-    utils::{icon::*, reader::NovelReader, shortcut, sql, update::UpdateChecker, window::*},
+    utils::{
+        icon::*,
+        import::{self, ImportTask},
+        library, shortcut, sql,
+        update::UpdateChecker,
+        watcher::FolderWatcher,
+        window::*,
+    },
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -66,22 +74,44 @@ pub fn run() {
             common::get_reading_mode,
             // 小说相关
             novel::add_novel,
+            novel::import_novels,
+            novel::cancel_import,
+            novel::is_importing,
             novel::get_novel_list,
             novel::open_novel,
             novel::close_novel,
             novel::delete_novel,
             novel::get_novel_detail,
+            novel::update_novel_metadata,
+            novel::set_novel_cover,
+            novel::get_encoding_candidates,
+            novel::set_novel_encoding,
+            novel::set_novel_chinese_conversion,
             // 阅读相关
             reader::get_novel_reader,
             reader::close_novel_reader,
             reader::get_line,
             reader::set_read_position,
+            // 章节规则相关
+            chapter::get_chapter_rule_presets,
+            chapter::get_chapter_rules,
+            chapter::set_chapter_rule_presets,
+            chapter::add_chapter_rule,
+            chapter::delete_chapter_rule,
+            chapter::preview_chapters,
+            // 替换规则相关
+            replace::get_replace_rules,
+            replace::add_replace_rule,
+            replace::set_replace_rule_enabled,
+            replace::delete_replace_rule,
+            replace::preview_replace_rule,
             // 配置相关
             config::get_config,
             config::reset_config,
             config::get_last_check_result,
             config::check_update,
             config::set_check_update_interval,
+            config::set_watched_folders,
             config::set_auto_start,
             config::set_language,
             config::set_theme,
@@ -89,6 +119,10 @@ pub fn run() {
             config::set_always_on_top,
             config::set_transparent,
             config::set_line_size,
+            config::set_fit_to_window,
+            config::set_chinese_conversion,
+            config::set_paragraph_navigation,
+            config::set_line_width,
             config::set_font_size,
             config::set_font_family,
             config::set_line_height,
@@ -119,21 +153,6 @@ pub fn run() {
             tauri::async_runtime::block_on(async {
                 let db = setup_db(app).await;
 
-                let mut novel_reader: Option<NovelReader> = None;
-
-                let line_size =
-                    get_from_app_store::<usize>(app.handle(), AppStoreKey::LineSize).unwrap();
-
-                if let Ok(novel) = sql::get_open_novel(&db).await {
-                    novel_reader = NovelReader::new(
-                        novel.id,
-                        novel.path,
-                        novel.read_position as usize,
-                        line_size,
-                    )
-                    .ok();
-                }
-
                 let interval =
                     get_from_app_store::<u64>(app.handle(), AppStoreKey::CheckUpdateInterval)
                         .unwrap();
@@ -142,14 +161,75 @@ pub fn run() {
 
                 update_checker.start(app.handle(), interval).unwrap();
 
+                let watched_folders =
+                    get_from_app_store::<Vec<String>>(app.handle(), AppStoreKey::WatchedFolders)
+                        .unwrap_or_default();
+
+                let mut folder_watcher = FolderWatcher::new();
+
+                // 监视文件夹的任务会立即扫描并导入，需要先注册数据库
                 app.manage(db);
+
+                folder_watcher.start(app.handle(), watched_folders).unwrap();
+
                 app.manage(Mutex::new(AppState {
-                    novel_reader,
+                    novel_reader: None,
                     reading_mode: false,
                     update_checker,
+                    import_task: ImportTask::new(),
+                    folder_watcher,
                 }));
             });
 
+            /* ------------------------------- 恢复上次打开的小说 ------------------------------- */
+
+            // 解析大文件较慢，在后台打开，完成后通知阅读器窗口刷新
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let db = app_handle.state::<Db>();
+
+                let Ok(novel) = sql::get_open_novel(&db).await else {
+                    return;
+                };
+
+                let Ok(novel_reader) = library::create_novel_reader(&app_handle, &db, novel).await
+                else {
+                    return;
+                };
+
+                let (document, options) =
+                    (novel_reader.document.clone(), novel_reader.options.clone());
+
+                {
+                    let state = app_handle.state::<Mutex<AppState>>();
+                    let mut state = state.lock().unwrap();
+
+                    // 打开期间用户已经打开了其他小说
+                    if state.novel_reader.is_some() {
+                        return;
+                    }
+
+                    state.novel_reader = Some(novel_reader);
+                }
+
+                document.remove_stale_cache(&options);
+
+                let _ = app_handle.emit(READER_CHANGE, ());
+            });
+
+            /* ---------------------------------- 补齐内容哈希 --------------------------------- */
+
+            // 旧版本导入的小说没有内容哈希，补齐需要读取每本小说的文件，在后台执行
+            // 未完成时导入与监视文件夹会等待补齐后再检查重复
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let db = app_handle.state::<Db>();
+
+                if let Err(e) = import::backfill_content_hashes(&db).await {
+                    log::error!("补齐内容哈希失败: {e}");
+                }
+            });
+
             /* --------------------------------- 开启时检查更新 -------------------------------- */
 
             let app_handle = app.handle().clone();
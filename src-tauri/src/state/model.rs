@@ -1,7 +1,11 @@
-use crate::utils::{reader::NovelReader, update::UpdateChecker};
+use crate::utils::{
+    import::ImportTask, reader::NovelReader, update::UpdateChecker, watcher::FolderWatcher,
+};
 
 pub struct AppState {
     pub novel_reader: Option<NovelReader>,
     pub reading_mode: bool,
     pub update_checker: UpdateChecker,
+    pub import_task: ImportTask,
+    pub folder_watcher: FolderWatcher,
 }
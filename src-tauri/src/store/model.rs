@@ -8,10 +8,15 @@ pub enum AppStoreKey {
     AutoStart,
     Language,
     Theme,
+    WatchedFolders,
     DockVisibility,
     AlwaysOnTop,
     Transparent,
     LineSize,
+    FitToWindow,
+    ChineseConversion,
+    ParagraphNavigation,
+    LineWidth,
     FontSize,
     FontFamily,
     LineHeight,
@@ -33,10 +38,15 @@ impl AppStoreKey {
             AppStoreKey::AutoStart => "auto_start",
             AppStoreKey::Language => "language",
             AppStoreKey::Theme => "theme",
+            AppStoreKey::WatchedFolders => "watched_folders",
             AppStoreKey::DockVisibility => "dock_visibility",
             AppStoreKey::AlwaysOnTop => "always_on_top",
             AppStoreKey::Transparent => "transparent",
             AppStoreKey::LineSize => "line_size",
+            AppStoreKey::FitToWindow => "fit_to_window",
+            AppStoreKey::ChineseConversion => "chinese_conversion",
+            AppStoreKey::ParagraphNavigation => "paragraph_navigation",
+            AppStoreKey::LineWidth => "line_width",
             AppStoreKey::FontSize => "font_size",
             AppStoreKey::FontFamily => "font_family",
             AppStoreKey::LineHeight => "line_height",
@@ -60,10 +70,15 @@ impl AppStoreKey {
             AppStoreKey::AutoStart => Value::Bool(DEFAULT_AUTO_START),
             AppStoreKey::Language => Value::String(DEFAULT_LANGUAGE.to_string()),
             AppStoreKey::Theme => Value::String(DEFAULT_THEME.to_string()),
+            AppStoreKey::WatchedFolders => Value::from(DEFAULT_WATCHED_FOLDERS),
             AppStoreKey::DockVisibility => Value::Bool(DEFAULT_DOCK_VISIBILITY),
             AppStoreKey::AlwaysOnTop => Value::Bool(DEFAULT_ALWAYS_ON_TOP),
             AppStoreKey::Transparent => Value::Bool(DEFAULT_TRANSPARENT),
             AppStoreKey::LineSize => Value::Number(Number::from(DEFAULT_LINE_SIZE)),
+            AppStoreKey::FitToWindow => Value::Bool(DEFAULT_FIT_TO_WINDOW),
+            AppStoreKey::ChineseConversion => Value::String(DEFAULT_CHINESE_CONVERSION.to_string()),
+            AppStoreKey::ParagraphNavigation => Value::Bool(DEFAULT_PARAGRAPH_NAVIGATION),
+            AppStoreKey::LineWidth => Value::Number(Number::from_f64(DEFAULT_LINE_WIDTH).unwrap()),
             AppStoreKey::FontSize => Value::Number(Number::from(DEFAULT_FONT_SIZE)),
             AppStoreKey::FontFamily => Value::String(DEFAULT_FONT_FAMILY.to_string()),
             AppStoreKey::LineHeight => {
@@ -95,10 +110,15 @@ impl AppStoreKey {
             AppStoreKey::AutoStart,
             AppStoreKey::Language,
             AppStoreKey::Theme,
+            AppStoreKey::WatchedFolders,
             AppStoreKey::DockVisibility,
             AppStoreKey::AlwaysOnTop,
             AppStoreKey::Transparent,
             AppStoreKey::LineSize,
+            AppStoreKey::FitToWindow,
+            AppStoreKey::ChineseConversion,
+            AppStoreKey::ParagraphNavigation,
+            AppStoreKey::LineWidth,
             AppStoreKey::FontSize,
             AppStoreKey::FontFamily,
             AppStoreKey::LineHeight,
@@ -0,0 +1,60 @@
+use std::{fs, io::Cursor, path::Path};
+
+use image::{codecs::jpeg::JpegEncoder, ImageReader};
+
+// 封面图片文件的大小上限（字节）
+const MAX_COVER_FILE_SIZE: u64 = 20 * 1024 * 1024;
+
+// 封面的最大宽高，超出时按比例缩小
+const MAX_COVER_WIDTH: u32 = 600;
+const MAX_COVER_HEIGHT: u32 = 900;
+
+// 封面统一保存为 JPEG
+const COVER_QUALITY: u8 = 85;
+
+// 读取图片文件作为封面
+pub fn read_cover_file(path: &Path) -> Result<Vec<u8>, String> {
+    let metadata = path
+        .metadata()
+        .map_err(|_| format!("图片文件不存在: {path:?}"))?;
+
+    if metadata.len() > MAX_COVER_FILE_SIZE {
+        return Err("图片文件过大，不能超过 20MB".to_string());
+    }
+
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+
+    normalize_cover(&bytes)
+}
+
+// 校验封面图片，过大时缩小，并重新编码为 JPEG
+pub fn normalize_cover(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+
+    if reader.format().is_none() {
+        return Err("不支持的图片格式".to_string());
+    }
+
+    let image = reader.decode().map_err(|e| format!("图片解析失败: {e}"))?;
+
+    if image.width() == 0 || image.height() == 0 {
+        return Err("图片尺寸无效".to_string());
+    }
+
+    let image = if image.width() > MAX_COVER_WIDTH || image.height() > MAX_COVER_HEIGHT {
+        image.thumbnail(MAX_COVER_WIDTH, MAX_COVER_HEIGHT)
+    } else {
+        image
+    };
+
+    let mut cover = Vec::new();
+
+    // JPEG 不支持透明通道，先转换为 RGB
+    JpegEncoder::new_with_quality(&mut cover, COVER_QUALITY)
+        .encode_image(&image.to_rgb8())
+        .map_err(|e| format!("封面编码失败: {e}"))?;
+
+    Ok(cover)
+}
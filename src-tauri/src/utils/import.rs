@@ -0,0 +1,642 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use charset_normalizer_rs::from_bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{async_runtime, AppHandle, Emitter, Manager};
+
+use crate::{
+    constants::event::*,
+    db::{model::Novel, Db},
+    state::model::AppState,
+    utils::{
+        cover, library,
+        reader::{
+            archive,
+            document::{file_hash, hex_digest, Document},
+            txt, NovelReader,
+        },
+        sql,
+    },
+};
+
+// 补齐内容哈希时持有，避免启动时的后台任务与导入同时计算同一本小说
+static BACKFILL_LOCK: async_runtime::Mutex<()> = async_runtime::Mutex::const_new(());
+
+// 导入的小说与已有的小说内容相同时的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateAction {
+    // 跳过，不导入
+    Skip,
+    // 用导入的文件替换已有小说的文件，保留阅读进度
+    Replace,
+    // 仍作为一本新的小说导入
+    Add,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Added,
+    Skipped,
+    Replaced,
+    // 发现重复且未指定处理方式，未导入
+    Duplicate,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportResult {
+    pub status: ImportStatus,
+    // 新增的小说，或与导入的文件内容相同的已有小说
+    pub novel_id: i64,
+    pub title: String,
+}
+
+// 导入小说文件或文件夹，on_duplicate 为空时遇到重复的小说不导入，由调用方决定如何处理
+pub async fn import_novel(
+    app_handle: &AppHandle,
+    db: &Db,
+    path: &str,
+    on_duplicate: Option<DuplicateAction>,
+) -> Result<ImportResult, String> {
+    let filepath = Path::new(path);
+
+    // 校验文件是否存在
+    if !filepath.exists() {
+        return Err(format!("文件不存在: {path}"));
+    }
+
+    let filename = filepath
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("无法获取文件名: {path}"))?;
+
+    let (stored_name, default_title) = if filepath.is_dir() {
+        // 文件夹中的章节文件打包为一个 zip 文件，作为一本小说导入
+        (format!("{filename}.zip"), filename)
+    } else {
+        let extension = filepath
+            .extension()
+            .ok_or_else(|| format!("无法获取文件扩展名: {path}"))?
+            .to_string_lossy()
+            .to_lowercase();
+
+        // 校验文件格式是否受支持
+        if !NovelReader::is_supported(path) {
+            return Err(format!("文件格式不支持: {filename}"));
+        }
+
+        // 纯文本格式，检查文件编码格式是否受支持
+        if ["txt", "md", "markdown", "html", "htm"].contains(&extension.as_str()) {
+            let path = path.to_string();
+            library::run_blocking(move || check_encoding(&path)).await?;
+        }
+
+        let default_title = match filename.split(".").next() {
+            Some(v) => v,
+            None => return Err(format!("非法文件名: {filename}")),
+        };
+
+        (filename.to_string(), default_title)
+    };
+
+    let library_dir = library::library_dir(app_handle)?;
+
+    // 先写入临时文件，入库后再重命名为正式文件，失败时不会留下文件或覆盖已有的文件
+    let temp_path = library::temp_import_path(&library_dir, &stored_name);
+
+    let result = import_temp_file(
+        app_handle,
+        db,
+        filepath,
+        TempImport {
+            library_dir: &library_dir,
+            temp_path: &temp_path,
+            stored_name: &stored_name,
+            source_path: path,
+        },
+        default_title,
+        on_duplicate,
+    )
+    .await;
+
+    // 导入成功时临时文件已被重命名，其余情况下删除临时文件
+    let _ = fs::remove_file(&temp_path);
+
+    result
+}
+
+// 检测整个文件的编码，大文件较慢
+fn check_encoding(path: &str) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut buf_reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+
+    buf_reader
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+
+    let result = from_bytes(&buffer, None);
+    result.get_best().ok_or("文件编码不支持")?;
+
+    Ok(())
+}
+
+// 将文件写入临时文件，检测重复后按指定的处理方式入库
+async fn import_temp_file(
+    app_handle: &AppHandle,
+    db: &Db,
+    filepath: &Path,
+    import: TempImport<'_>,
+    default_title: &str,
+    on_duplicate: Option<DuplicateAction>,
+) -> Result<ImportResult, String> {
+    let content_hash = {
+        let filepath = filepath.to_path_buf();
+        let temp_path = import.temp_path.to_path_buf();
+
+        library::run_blocking(move || {
+            if filepath.is_dir() {
+                archive::pack_directory(&filepath, &temp_path)?;
+            } else {
+                fs::copy(&filepath, &temp_path).map_err(|e| e.to_string())?;
+            }
+
+            content_hash(&temp_path)
+        })
+        .await?
+    };
+
+    match (find_duplicate(db, &content_hash).await?, on_duplicate) {
+        (None, _) | (Some(_), Some(DuplicateAction::Add)) => {
+            import.add(db, default_title, &content_hash).await
+        }
+        (Some(novel), None) => Ok(ImportResult {
+            status: ImportStatus::Duplicate,
+            novel_id: novel.id,
+            title: novel.title,
+        }),
+        (Some(novel), Some(DuplicateAction::Skip)) => Ok(ImportResult {
+            status: ImportStatus::Skipped,
+            novel_id: novel.id,
+            title: novel.title,
+        }),
+        (Some(novel), Some(DuplicateAction::Replace)) => {
+            import.replace(app_handle, db, novel, &content_hash).await
+        }
+    }
+}
+
+// 内容哈希：纯文本按解码后去除空白的正文计算，编码或换行不同的同一本小说也能识别；其余格式按文件内容计算
+pub fn content_hash(path: &Path) -> Result<String, String> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| format!("文件路径转换失败: {path:?}"))?;
+
+    let is_txt = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("txt"));
+
+    if !is_txt {
+        return file_hash(path);
+    }
+
+    let text: String = txt::read_text(path, None)?
+        .chars()
+        .filter(|char| !char.is_whitespace())
+        .collect();
+
+    Ok(hex_digest(Sha256::digest(text.as_bytes())))
+}
+
+// 查找内容相同的小说，启动时在后台补齐内容哈希，尚未补齐时先补齐再查找
+async fn find_duplicate(db: &Db, content_hash: &str) -> Result<Option<Novel>, String> {
+    backfill_content_hashes(db).await?;
+
+    sql::find_novel_by_content_hash(db, content_hash).await
+}
+
+// 补齐旧版本导入的小说的内容哈希，启动时在后台执行，导入时也会检查一次
+// 文件已丢失等无法计算的小说记为空字符串，不再重复计算，也不会与任何小说重复
+pub async fn backfill_content_hashes(db: &Db) -> Result<(), String> {
+    // 同时只有一处在补齐，后来的调用等待完成后不再有需要补齐的小说
+    let _guard = BACKFILL_LOCK.lock().await;
+
+    for novel in sql::get_novels_without_content_hash(db).await? {
+        let path = novel.path.clone();
+
+        let hash = library::run_blocking(move || self::content_hash(Path::new(&path)))
+            .await
+            .unwrap_or_default();
+
+        sql::set_novel_content_hash(db, novel.id, &hash).await?;
+    }
+
+    Ok(())
+}
+
+// 已写入书库目录的临时文件
+struct TempImport<'a> {
+    library_dir: &'a Path,
+    temp_path: &'a Path,
+    stored_name: &'a str,
+    // 导入的源文件路径
+    source_path: &'a str,
+}
+
+impl TempImport<'_> {
+    // 作为新的小说入库，提交事务前将临时文件重命名为书库中不重名的正式文件
+    async fn add(
+        &self,
+        db: &Db,
+        default_title: &str,
+        content_hash: &str,
+    ) -> Result<ImportResult, String> {
+        let temp_path_str = self
+            .temp_path
+            .to_str()
+            .ok_or_else(|| format!("文件路径转换失败: {:?}", self.temp_path))?
+            .to_string();
+
+        let metadata = library::run_blocking(move || {
+            NovelReader::check_content(&temp_path_str)?;
+
+            let mut metadata = NovelReader::read_metadata(&temp_path_str)?;
+
+            // 文件中的封面同样缩小后保存，无法解析的封面直接丢弃
+            metadata.cover = metadata
+                .cover
+                .and_then(|cover| cover::normalize_cover(&cover).ok());
+
+            Ok(metadata)
+        })
+        .await?;
+
+        // 文件中没有书名时使用文件名
+        let title = metadata
+            .title
+            .clone()
+            .unwrap_or_else(|| default_title.to_string());
+
+        let new_path = library::reserve_library_path(self.library_dir, self.stored_name)?;
+
+        let result: Result<i64, String> = async {
+            let new_path_str = new_path
+                .to_str()
+                .ok_or_else(|| format!("文件路径转换失败: {new_path:?}"))?;
+
+            let mut tx = db.begin().await.map_err(|e| e.to_string())?;
+
+            let novel_id = sql::add_novel(
+                &mut *tx,
+                &title,
+                metadata,
+                new_path_str,
+                self.file_size()?,
+                content_hash,
+                self.source_path,
+            )
+            .await?;
+
+            self.commit(tx, &new_path).await?;
+
+            Ok(novel_id)
+        }
+        .await;
+
+        let novel_id = release_on_error(result, &new_path)?;
+
+        Ok(ImportResult {
+            status: ImportStatus::Added,
+            novel_id,
+            title,
+        })
+    }
+
+    // 替换已有小说的文件，保留书名、阅读进度等信息
+    async fn replace(
+        &self,
+        app_handle: &AppHandle,
+        db: &Db,
+        novel: Novel,
+        content_hash: &str,
+    ) -> Result<ImportResult, String> {
+        let new_path = library::reserve_library_path(self.library_dir, self.stored_name)?;
+
+        let result: Result<(), String> = async {
+            let new_path_str = new_path
+                .to_str()
+                .ok_or_else(|| format!("文件路径转换失败: {new_path:?}"))?;
+
+            let mut tx = db.begin().await.map_err(|e| e.to_string())?;
+
+            sql::replace_novel_file(
+                &mut *tx,
+                novel.id,
+                new_path_str,
+                self.file_size()?,
+                content_hash,
+                self.source_path,
+            )
+            .await?;
+
+            self.commit(tx, &new_path).await
+        }
+        .await;
+
+        release_on_error(result, &new_path)?;
+
+        reopen_novel_reader(app_handle, db, novel.id).await?;
+
+        // 旧文件不再被其他小说使用时删除，旧的阅读器已被替换，其解析缓存一并删除
+        if sql::count_novels_by_path(db, &novel.path).await? == 0 {
+            let _ = fs::remove_file(&novel.path);
+
+            if let Some(cache_dir) = library::document_cache_dir(app_handle) {
+                Document::remove_cache(&cache_dir, &novel.path);
+            }
+        }
+
+        Ok(ImportResult {
+            status: ImportStatus::Replaced,
+            novel_id: novel.id,
+            title: novel.title,
+        })
+    }
+
+    fn file_size(&self) -> Result<i64, String> {
+        self.temp_path
+            .metadata()
+            .map(|metadata| metadata.len() as i64)
+            .map_err(|e| e.to_string())
+    }
+
+    // 用临时文件覆盖占用的书库文件后提交事务；重命名失败时事务随之丢弃，提交失败时撤销重命名
+    async fn commit(
+        &self,
+        tx: sqlx::Transaction<'_, sqlx::Sqlite>,
+        new_path: &Path,
+    ) -> Result<(), String> {
+        fs::rename(self.temp_path, new_path).map_err(|e| e.to_string())?;
+
+        if let Err(e) = tx.commit().await {
+            let _ = fs::rename(new_path, self.temp_path);
+            return Err(e.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// 入库失败时删除为其占用的书库文件
+fn release_on_error<T>(result: Result<T, String>, reserved_path: &Path) -> Result<T, String> {
+    if result.is_err() {
+        let _ = fs::remove_file(reserved_path);
+    }
+
+    result
+}
+
+// 正在阅读的小说文件被替换后，按新文件重新打开，阅读位置锚点保持不变
+async fn reopen_novel_reader(app_handle: &AppHandle, db: &Db, novel_id: i64) -> Result<(), String> {
+    let state = app_handle.state::<Mutex<AppState>>();
+
+    let is_open = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        state
+            .novel_reader
+            .as_ref()
+            .is_some_and(|reader| reader.novel_id == novel_id)
+    };
+
+    if !is_open {
+        return Ok(());
+    }
+
+    let novel = sql::get_novel_by_id(db, novel_id).await?;
+    let reader = library::create_novel_reader(app_handle, db, novel).await?;
+    let (document, options) = (reader.document.clone(), reader.options.clone());
+
+    {
+        let mut state = state.lock().map_err(|e| e.to_string())?;
+        state.novel_reader = Some(reader);
+    }
+
+    document.remove_stale_cache(&options);
+
+    app_handle.emit(READER_CHANGE, ()).unwrap();
+
+    Ok(())
+}
+
+// 批量导入中单个文件的进度
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportProgress {
+    // 从 1 开始的序号
+    pub current: usize,
+    pub total: usize,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportSucceeded {
+    pub path: String,
+    pub result: ImportResult,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportFailed {
+    pub path: String,
+    pub error: String,
+}
+
+// 批量导入结束时的统计
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ImportSummary {
+    pub total: usize,
+    pub added: usize,
+    pub skipped: usize,
+    pub replaced: usize,
+    pub duplicate: usize,
+    pub failed: usize,
+    // 是否被取消，取消时未处理的文件不计入统计
+    pub cancelled: bool,
+}
+
+// 后台批量导入任务，同一时间只运行一个
+pub struct ImportTask {
+    running: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ImportTask {
+    pub fn new() -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    // 在后台依次导入文件，文件夹中的文件各自作为一本小说导入
+    pub fn start(
+        &mut self,
+        app_handle: &AppHandle,
+        paths: Vec<String>,
+        recursive: bool,
+        on_duplicate: Option<DuplicateAction>,
+    ) -> Result<(), String> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return Err("已有正在进行的导入任务".to_string());
+        }
+
+        self.cancelled.store(false, Ordering::SeqCst);
+
+        let app_handle = app_handle.clone();
+        let running = self.running.clone();
+        let cancelled = self.cancelled.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let summary = Self::run(&app_handle, &paths, recursive, on_duplicate, &cancelled).await;
+
+            running.store(false, Ordering::SeqCst);
+
+            app_handle.emit(IMPORT_FINISHED, summary).unwrap();
+        });
+
+        Ok(())
+    }
+
+    // 取消导入，正在导入的文件完成后停止
+    pub fn cancel(&self) {
+        if self.running.load(Ordering::SeqCst) {
+            self.cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    async fn run(
+        app_handle: &AppHandle,
+        paths: &[String],
+        recursive: bool,
+        on_duplicate: Option<DuplicateAction>,
+        cancelled: &AtomicBool,
+    ) -> ImportSummary {
+        let db = app_handle.state::<Db>();
+
+        let files = {
+            let paths = paths.to_vec();
+            library::run_blocking(move || Ok(collect_files(&paths, recursive)))
+                .await
+                .unwrap_or_default()
+        };
+
+        let mut summary = ImportSummary {
+            total: files.len(),
+            ..Default::default()
+        };
+
+        log::info!(target: "ImportTask", "开始导入 {} 个文件", summary.total);
+
+        for (index, file) in files.iter().enumerate() {
+            if cancelled.load(Ordering::SeqCst) {
+                log::info!(target: "ImportTask", "导入已取消");
+                summary.cancelled = true;
+                break;
+            }
+
+            let path = file.to_string_lossy().to_string();
+
+            let progress = ImportProgress {
+                current: index + 1,
+                total: summary.total,
+                path: path.clone(),
+            };
+
+            app_handle.emit(IMPORT_PROGRESS_CHANGE, progress).unwrap();
+
+            match import_novel(app_handle, &db, &path, on_duplicate).await {
+                Ok(result) => {
+                    match result.status {
+                        ImportStatus::Added => summary.added += 1,
+                        ImportStatus::Skipped => summary.skipped += 1,
+                        ImportStatus::Replaced => summary.replaced += 1,
+                        ImportStatus::Duplicate => summary.duplicate += 1,
+                    }
+
+                    app_handle
+                        .emit(IMPORT_SUCCEEDED, ImportSucceeded { path, result })
+                        .unwrap();
+                }
+                Err(error) => {
+                    log::error!(target: "ImportTask", "导入失败 {path}: {error}");
+                    summary.failed += 1;
+
+                    app_handle
+                        .emit(IMPORT_FAILED, ImportFailed { path, error })
+                        .unwrap();
+                }
+            }
+        }
+
+        log::info!(target: "ImportTask", "导入完成");
+
+        summary
+    }
+}
+
+// 展开待导入的路径：文件原样保留，文件夹中只收集受支持的文件，跳过隐藏文件
+pub fn collect_files(paths: &[String], recursive: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        let path = PathBuf::from(path);
+
+        if path.is_dir() {
+            collect_dir(&path, recursive, &mut files);
+        } else {
+            files.push(path);
+        }
+    }
+
+    files
+}
+
+fn collect_dir(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut entries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| !name.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+
+    // 按文件名排序，导入顺序与文件管理器中一致
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            if recursive {
+                collect_dir(&path, recursive, files);
+            }
+        } else if path.to_str().is_some_and(NovelReader::is_supported) {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    constants::event::*,
+    db::{
+        model::{Novel, ReplaceRule},
+        Db,
+    },
+    state::model::AppState,
+    store::{get_from_app_store, model::AppStoreKey},
+    utils::{
+        reader::{
+            chapter::resolve_chapter_patterns,
+            cleanup::{CleanupRule, ReplaceRuleKind},
+            convert::ChineseConversion,
+            document::Document,
+            measure::PixelFit,
+            NovelReader, ReadAnchor, ReaderOptions, SUPPORTED_EXTENSIONS,
+        },
+        sql,
+    },
+};
+
+// 根据全局配置与小说自身的设置，生成解析小说所需的参数
+pub async fn get_reader_options(
+    app_handle: &AppHandle,
+    db: &Db,
+    novel: &Novel,
+) -> Result<ReaderOptions, String> {
+    let line_size = get_from_app_store::<usize>(app_handle, AppStoreKey::LineSize).unwrap();
+    let fit_to_window = get_from_app_store::<bool>(app_handle, AppStoreKey::FitToWindow).unwrap();
+    let line_width = get_from_app_store::<f64>(app_handle, AppStoreKey::LineWidth).unwrap();
+
+    // 阅读器窗口尚未上报宽度时，仍按每行字数排版
+    let pixel_fit = (fit_to_window && line_width > 0.0).then(|| PixelFit {
+        line_width,
+        font_family: get_from_app_store(app_handle, AppStoreKey::FontFamily).unwrap(),
+        font_size: get_from_app_store(app_handle, AppStoreKey::FontSize).unwrap(),
+        font_weight: get_from_app_store(app_handle, AppStoreKey::FontWeight).unwrap(),
+        letter_spacing: get_from_app_store(app_handle, AppStoreKey::LetterSpacing).unwrap(),
+    });
+
+    let custom_patterns: Vec<String> = sql::get_chapter_rules(db, novel.id)
+        .await?
+        .into_iter()
+        .map(|rule| rule.pattern)
+        .collect();
+
+    let chapter_patterns =
+        resolve_chapter_patterns(novel.chapter_rule_presets.as_deref(), &custom_patterns);
+
+    let conversion = resolve_chinese_conversion(app_handle, novel);
+
+    let cleanup_rules = get_cleanup_rules(db, Some(novel.id)).await?;
+
+    Ok(ReaderOptions {
+        line_size,
+        chapter_patterns,
+        pixel_fit,
+        cache_dir: document_cache_dir(app_handle),
+        encoding: novel.encoding.clone(),
+        conversion,
+        cleanup_rules,
+    })
+}
+
+// 获取已启用的替换规则，novel_id 为空时只获取全局规则
+pub async fn get_cleanup_rules(db: &Db, novel_id: Option<i64>) -> Result<Vec<CleanupRule>, String> {
+    let rules = sql::get_replace_rules(db, novel_id)
+        .await?
+        .iter()
+        .filter(|rule| rule.enabled != 0)
+        .filter_map(to_cleanup_rule)
+        .collect();
+
+    Ok(rules)
+}
+
+pub fn to_cleanup_rule(rule: &ReplaceRule) -> Option<CleanupRule> {
+    Some(CleanupRule {
+        kind: ReplaceRuleKind::from_key(&rule.kind)?,
+        pattern: rule.pattern.clone(),
+        replacement: rule.replacement.clone(),
+    })
+}
+
+// 小说单独设置的简繁转换优先，否则使用全局设置
+pub fn resolve_chinese_conversion(app_handle: &AppHandle, novel: &Novel) -> ChineseConversion {
+    novel
+        .chinese_conversion
+        .as_deref()
+        .and_then(ChineseConversion::from_key)
+        .or_else(|| get_from_app_store(app_handle, AppStoreKey::ChineseConversion))
+        .unwrap_or_default()
+}
+
+pub async fn create_novel_reader(
+    app_handle: &AppHandle,
+    db: &Db,
+    novel: Novel,
+) -> Result<NovelReader, String> {
+    let options = get_reader_options(app_handle, db, &novel).await?;
+
+    let read_anchor = match (novel.read_chapter, novel.read_paragraph, novel.read_offset) {
+        (Some(chapter), Some(paragraph), Some(offset)) => Some(ReadAnchor {
+            chapter: chapter as usize,
+            paragraph: paragraph as usize,
+            offset: offset as usize,
+        }),
+        _ => None,
+    };
+
+    // 解析大文件需要较长时间，放到阻塞线程中执行，避免占用异步运行时
+    tauri::async_runtime::spawn_blocking(move || {
+        NovelReader::new(
+            novel.id,
+            novel.path,
+            novel.read_position as usize,
+            read_anchor,
+            options,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// 小说的解析或排版设置变化后，若该小说正在阅读，则重新解析，并按阅读位置锚点保持阅读位置
+pub async fn reload_novel_reader(
+    app_handle: &AppHandle,
+    db: &Db,
+    novel_id: i64,
+) -> Result<(), String> {
+    let state = app_handle.state::<Mutex<AppState>>();
+
+    // 章节规则、编码等解析设置未变时复用已解析的文档，只重新排版
+    let (parse_key, document) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        match state
+            .novel_reader
+            .as_ref()
+            .filter(|reader| reader.novel_id == novel_id)
+        {
+            Some(reader) => (reader.options.parse_key(), reader.document.clone()),
+            None => return Ok(()),
+        }
+    };
+
+    let novel = sql::get_novel_by_id(db, novel_id).await?;
+    let options = get_reader_options(app_handle, db, &novel).await?;
+
+    let (document, layout) = {
+        let options = options.clone();
+
+        tauri::async_runtime::spawn_blocking(move || {
+            let document = if parse_key == options.parse_key() {
+                document
+            } else {
+                Arc::new(Document::load(&novel.path, &options)?)
+            };
+
+            let layout = document.layout(&options)?;
+
+            Ok::<_, String>((document, layout))
+        })
+        .await
+        .map_err(|e| e.to_string())??
+    };
+
+    let (read_position, read_progress, read_anchor, document, options) = {
+        let mut state = state.lock().map_err(|e| e.to_string())?;
+        let Some(reader) = state
+            .novel_reader
+            .as_mut()
+            .filter(|reader| reader.novel_id == novel_id)
+        else {
+            return Ok(());
+        };
+
+        reader.relayout(document, layout, options)?;
+
+        (
+            reader.read_position as i64,
+            reader.read_progress(),
+            reader.read_anchor(),
+            reader.document.clone(),
+            reader.options.clone(),
+        )
+    };
+
+    // 旧的阅读器已被替换，按旧设置生成的缓存不再使用
+    document.remove_stale_cache(&options);
+
+    sql::save_novel(db, novel_id, read_position, read_progress, &read_anchor).await?;
+
+    app_handle
+        .emit(READER_CHANGE, ())
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// 排版设置（每行字数、字体、窗口宽度等）变化后，重新排版正在阅读的小说
+pub async fn relayout_novel_reader(app_handle: &AppHandle, db: &Db) -> Result<(), String> {
+    let novel_id = {
+        let state = app_handle.state::<Mutex<AppState>>();
+        let state = state.lock().map_err(|e| e.to_string())?;
+        state.novel_reader.as_ref().map(|reader| reader.novel_id)
+    };
+
+    match novel_id {
+        Some(novel_id) => reload_novel_reader(app_handle, db, novel_id).await,
+        None => Ok(()),
+    }
+}
+
+// 解析结果的缓存目录
+pub fn document_cache_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("documents"))
+        .ok()
+}
+
+// 书库目录，导入的小说文件都保存在这里
+pub fn library_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("library");
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    Ok(dir)
+}
+
+// 在书库目录中为导入的文件分配不重名的路径，同名时在文件名后追加序号
+// 以独占方式创建空文件占用该路径，同时进行的导入不会分配到同一个路径而互相覆盖
+pub fn reserve_library_path(library_dir: &Path, filename: &str) -> Result<PathBuf, String> {
+    let (stem, extension) = split_file_name(filename);
+
+    for index in 1.. {
+        let path = match index {
+            1 => library_dir.join(filename),
+            _ => library_dir.join(format!("{stem} ({index}){extension}")),
+        };
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return Ok(path),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("创建书库文件失败: {e}")),
+        }
+    }
+
+    unreachable!()
+}
+
+// 导入过程中使用的临时文件，与目标文件在同一目录，保留扩展名以便识别格式
+pub fn temp_import_path(library_dir: &Path, filename: &str) -> PathBuf {
+    let timestamp = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+
+    library_dir.join(format!(".importing-{timestamp}-{filename}"))
+}
+
+// 拆分文件名与扩展名，fb2.zip 等多段扩展名作为一个整体
+fn split_file_name(filename: &str) -> (&str, &str) {
+    let extension_len = SUPPORTED_EXTENSIONS
+        .iter()
+        .map(|extension| extension.len() + 1)
+        .filter(|len| {
+            filename.len() > *len
+                && filename.is_char_boundary(filename.len() - len)
+                && filename[filename.len() - len..]
+                    .to_lowercase()
+                    .strip_prefix('.')
+                    .is_some_and(|extension| SUPPORTED_EXTENSIONS.contains(&extension))
+        })
+        .max()
+        .unwrap_or(0);
+
+    filename.split_at(filename.len() - extension_len)
+}
+
+// 解析、解码、哈希与复制文件等耗时操作放到阻塞线程中执行，避免占用异步运行时
+pub async fn run_blocking<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -1,6 +1,10 @@
+pub mod cover;
 pub mod icon;
+pub mod import;
+pub mod library;
 pub mod reader;
 pub mod shortcut;
 pub mod sql;
 pub mod update;
+pub mod watcher;
 pub mod window;
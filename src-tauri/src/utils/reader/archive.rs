@@ -0,0 +1,297 @@
+use std::{
+    cmp::Ordering,
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use regex::Regex;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{push_paragraph, txt::decode_text, FileReader, Paragraph, ReaderOptions};
+
+// 压缩包或文件夹中作为章节读取的文件
+const CHAPTER_EXTENSIONS: &[&str] = &["txt"];
+
+// 章节标题最长字数，超过时不把首行当作标题
+const MAX_TITLE_CHARS: usize = 30;
+
+// 章节文件解压后的总大小上限，防止压缩炸弹耗尽内存
+const MAX_UNCOMPRESSED_BYTES: u64 = 256 * 1024 * 1024;
+
+// 每个文件一章的压缩包
+pub struct ArchiveReader;
+
+impl FileReader for ArchiveReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+        let mut members: Vec<(String, usize)> = Vec::new();
+        let mut declared_size: u64 = 0;
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index).map_err(|e| e.to_string())?;
+
+            if entry.is_dir() {
+                continue;
+            }
+
+            let name = entry_name(entry.name_raw());
+            if is_chapter_file(&name) {
+                declared_size = declared_size.saturating_add(entry.size());
+                members.push((name, index));
+            }
+        }
+
+        if members.is_empty() {
+            return Err(
+                "压缩包中没有 txt 文本文件，暂不支持压缩包中的其他格式，请解压后单独导入"
+                    .to_string(),
+            );
+        }
+
+        if declared_size > MAX_UNCOMPRESSED_BYTES {
+            return Err(too_large_error());
+        }
+
+        members.sort_by(|(a, _), (b, _)| natural_cmp(a, b));
+
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+        let mut remaining = MAX_UNCOMPRESSED_BYTES;
+
+        for (name, index) in members {
+            let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+            let buffer = read_limited(entry, &mut remaining)?;
+
+            // 每个文件单独检测编码
+            let content = decode_text(buffer, options.encoding.as_deref())?;
+            let mut raw_lines = content
+                .lines()
+                .map(str::trim)
+                .skip_while(|line| line.is_empty());
+
+            let first_line = raw_lines.next().unwrap_or_default();
+
+            let (title, first_body_line) = match title_from_name(&name) {
+                // 首行是以文件名标题开头的完整标题时使用首行
+                Some(title) if is_title_line(first_line, &title) => (first_line.to_string(), None),
+                Some(title) => (title, Some(first_line)),
+                // 文件名只有序号时使用首行
+                None if !first_line.is_empty() && first_line.chars().count() <= MAX_TITLE_CHARS => {
+                    (first_line.to_string(), None)
+                }
+                None => (stem(&name).to_string(), Some(first_line)),
+            };
+
+            paragraphs.push(Paragraph {
+                is_chapter: true,
+                text: title,
+            });
+
+            for line in first_body_line.into_iter().chain(raw_lines) {
+                push_paragraph(&mut paragraphs, line);
+            }
+        }
+
+        Ok(paragraphs)
+    }
+}
+
+// 读取一个文件，累计读取的字节数超过剩余额度时报错
+// 文件头中记录的大小可能是伪造的，需要按实际解压出的字节数再限制一次
+fn read_limited(reader: impl Read, remaining: &mut u64) -> Result<Vec<u8>, String> {
+    let mut buffer = Vec::new();
+
+    reader
+        .take(*remaining + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+
+    *remaining = remaining
+        .checked_sub(buffer.len() as u64)
+        .ok_or_else(too_large_error)?;
+
+    Ok(buffer)
+}
+
+fn too_large_error() -> String {
+    format!(
+        "压缩包解压后超过 {} MB，无法导入",
+        MAX_UNCOMPRESSED_BYTES / 1024 / 1024
+    )
+}
+
+// 将文件夹中的章节文件（含子文件夹）打包为 zip 文件
+pub fn pack_directory(dir: &Path, target: &Path) -> Result<(), String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+
+    files.retain(|name| is_chapter_file(name));
+
+    if files.is_empty() {
+        return Err("文件夹中没有 txt 文本文件".to_string());
+    }
+
+    let file = File::create(target).map_err(|e| e.to_string())?;
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for name in files {
+        let content = fs::read(dir.join(&name)).map_err(|e| e.to_string())?;
+
+        writer
+            .start_file(name, options)
+            .map_err(|e| e.to_string())?;
+        writer.write_all(&content).map_err(|e| e.to_string())?;
+    }
+
+    writer.finish().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let name = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_chapter_file(name: &str) -> bool {
+    let file_name = name.rsplit('/').next().unwrap_or(name);
+
+    // 忽略 macOS 生成的元数据文件
+    if name.starts_with("__MACOSX/") || file_name.starts_with("._") {
+        return false;
+    }
+
+    Path::new(file_name).extension().is_some_and(|extension| {
+        CHAPTER_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str())
+    })
+}
+
+// 压缩包中的文件名：未标记为 UTF-8 的文件名（如 Windows 下的 GBK）按检测到的编码解码
+fn entry_name(raw: &[u8]) -> String {
+    match std::str::from_utf8(raw) {
+        Ok(name) => name.to_string(),
+        Err(_) => decode_text(raw.to_vec(), None)
+            .unwrap_or_else(|_| String::from_utf8_lossy(raw).to_string()),
+    }
+}
+
+fn stem(name: &str) -> &str {
+    let file_name = name.rsplit('/').next().unwrap_or(name);
+
+    file_name
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(file_name)
+}
+
+// 去掉文件名开头的序号，如 “0001 序章.txt” 的标题为 “序章”
+fn title_from_name(name: &str) -> Option<String> {
+    let prefix = Regex::new(r"^[\d\s._\-、()\[\]（）【】]+").unwrap();
+    let title = prefix.replace(stem(name), "").trim().to_string();
+
+    (!title.is_empty()).then_some(title)
+}
+
+// 首行是否为以文件名标题开头的标题，如文件名为 “第一章”，首行为 “第一章” 或 “第一章 风起”
+fn is_title_line(line: &str, title: &str) -> bool {
+    if line.chars().count() > MAX_TITLE_CHARS {
+        return false;
+    }
+
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let title: String = title.chars().filter(|c| !c.is_whitespace()).collect();
+
+    line.starts_with(&title)
+}
+
+// 自然排序：数字部分按数值比较，“2.txt” 排在 “10.txt” 之前
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let mut a_number = String::new();
+                while let Some(c) = a_chars.next_if(char::is_ascii_digit) {
+                    a_number.push(c);
+                }
+
+                let mut b_number = String::new();
+                while let Some(c) = b_chars.next_if(char::is_ascii_digit) {
+                    b_number.push(c);
+                }
+
+                let a_trimmed = a_number.trim_start_matches('0');
+                let b_trimmed = b_number.trim_start_matches('0');
+
+                let ordering = a_trimmed
+                    .len()
+                    .cmp(&b_trimmed.len())
+                    .then_with(|| a_trimmed.cmp(b_trimmed))
+                    .then_with(|| a_number.len().cmp(&b_number.len()));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char.to_lowercase().cmp(b_char.to_lowercase());
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_order() {
+        assert_eq!(natural_cmp("2.txt", "10.txt"), Ordering::Less);
+        assert_eq!(natural_cmp("第9章", "第10章"), Ordering::Less);
+        assert_eq!(natural_cmp("a2", "a02"), Ordering::Less);
+        assert_eq!(natural_cmp("a", "B"), Ordering::Less);
+        assert_eq!(natural_cmp("a1", "a1"), Ordering::Equal);
+        assert_eq!(natural_cmp("a1b", "a1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn limits_uncompressed_size() {
+        let mut remaining = 10;
+
+        assert_eq!(
+            read_limited(&b"hello"[..], &mut remaining).unwrap(),
+            b"hello"
+        );
+        assert_eq!(remaining, 5);
+        assert!(read_limited(&b"world"[..], &mut remaining).is_ok());
+        assert!(read_limited(&b"!"[..], &mut remaining).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use regex::Regex;
+use serde::Serialize;
+
+use super::heuristic;
+
+// 中文数字（含大写数字与全角数字）
+const CHINESE_NUMBER: &str = "0-9０-９零〇一二两三四五六七八九十百千万壹贰叁肆伍陆柒捌玖拾佰仟";
+
+// 英文数字单词
+const ENGLISH_NUMBER: &str = "one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve|thirteen|fourteen|fifteen|sixteen|seventeen|eighteen|nineteen|twenty|thirty|forty|fifty|sixty|seventy|eighty|ninety|hundred";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ChapterPreview {
+    pub index: usize,
+    pub title: String,
+    pub start_line: usize,
+    // 章节行数（含标题行）
+    pub line_count: usize,
+    // 章节字数（含标题）
+    pub char_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ChapterRulePreset {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub pattern: String,
+    pub is_default: bool,
+}
+
+impl ChapterRulePreset {
+    pub fn all() -> Vec<ChapterRulePreset> {
+        vec![
+            ChapterRulePreset {
+                key: "zh_chapter",
+                name: "第X章/节/回",
+                pattern: format!(r"^第\s*[{CHINESE_NUMBER}]+\s*[章节節回话話].{{0,30}}$"),
+                is_default: true,
+            },
+            ChapterRulePreset {
+                key: "zh_volume",
+                name: "第X卷/部/篇/集",
+                pattern: format!(
+                    r"^(?:第\s*[{CHINESE_NUMBER}]+\s*[卷部篇集]|卷\s*[{CHINESE_NUMBER}]+).{{0,30}}$"
+                ),
+                is_default: true,
+            },
+            ChapterRulePreset {
+                key: "zh_special",
+                name: "序章/楔子/番外/后记",
+                pattern: format!(
+                    r"^(?:序章|序言|序|楔子|引子|引言|前言|尾声|尾聲|后记|後記|终章|終章|番外|完本感言)(?:[\s:：、.．·\-—].{{0,30}}|[{CHINESE_NUMBER}]{{0,4}})$"
+                ),
+                is_default: true,
+            },
+            ChapterRulePreset {
+                key: "en_chapter",
+                name: "Chapter/Part/Book X",
+                pattern: format!(
+                    r"^(?i:chapter|chap\.|part|book|volume|vol\.)\s+(?:\d+|[IVXLCDM]+|(?i:(?:twenty|thirty|forty|fifty|sixty|seventy|eighty|ninety)-(?:one|two|three|four|five|six|seven|eight|nine)|{ENGLISH_NUMBER}))\b.{{0,60}}$"
+                ),
+                is_default: true,
+            },
+            ChapterRulePreset {
+                key: "en_special",
+                name: "Prologue/Epilogue",
+                pattern: r"^(?i:prologue|epilogue|preface|foreword|introduction|afterword|interlude)(?:\s*[:.\-—]\s*.{0,60})?$"
+                    .to_string(),
+                is_default: true,
+            },
+            ChapterRulePreset {
+                key: "numbered",
+                name: "纯数字编号（如 1、标题）",
+                pattern: r"^[0-9０-９]{1,4}[、.．]\s*\S.{0,30}$".to_string(),
+                is_default: false,
+            },
+        ]
+    }
+
+    pub fn find(key: &str) -> Option<ChapterRulePreset> {
+        Self::all().into_iter().find(|preset| preset.key == key)
+    }
+}
+
+// 根据小说选择的预设（以逗号分隔的 key，为空时使用默认预设）与自定义规则，得到最终的章节正则列表
+pub fn resolve_chapter_patterns(presets: Option<&str>, custom_patterns: &[String]) -> Vec<String> {
+    let presets: Vec<ChapterRulePreset> = match presets {
+        Some(presets) => presets
+            .split(',')
+            .filter_map(|key| ChapterRulePreset::find(key.trim()))
+            .collect(),
+        None => ChapterRulePreset::all()
+            .into_iter()
+            .filter(|preset| preset.is_default)
+            .collect(),
+    };
+
+    presets
+        .into_iter()
+        .map(|preset| preset.pattern)
+        .chain(custom_patterns.iter().cloned())
+        .collect()
+}
+
+pub struct ChapterMatcher {
+    regexes: Vec<Regex>,
+}
+
+impl ChapterMatcher {
+    pub fn new(patterns: &[String]) -> Result<Self, String> {
+        let regexes = patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern).map_err(|e| format!("章节规则无效: {pattern}: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { regexes })
+    }
+
+    pub fn is_match(&self, line: &str) -> bool {
+        !line.is_empty() && self.regexes.iter().any(|regex| regex.is_match(line))
+    }
+}
+
+// 标记每一行是否为章节标题：优先按章节规则匹配，没有任何规则命中时退回到启发式检测
+pub fn detect_chapter_lines(lines: &[&str], patterns: &[String]) -> Result<Vec<bool>, String> {
+    let chapter_matcher = ChapterMatcher::new(patterns)?;
+
+    let mut chapter_line_indexes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| chapter_matcher.is_match(line))
+        .map(|(index, _)| index)
+        .collect();
+
+    if chapter_line_indexes.is_empty() {
+        chapter_line_indexes = heuristic::detect_chapter_lines(lines);
+    }
+
+    let mut is_chapter_line = vec![false; lines.len()];
+    for index in chapter_line_indexes {
+        is_chapter_line[index] = true;
+    }
+
+    Ok(is_chapter_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset_matches(key: &str, line: &str) -> bool {
+        let preset = ChapterRulePreset::find(key).unwrap();
+        ChapterMatcher::new(&[preset.pattern])
+            .unwrap()
+            .is_match(line)
+    }
+
+    #[test]
+    fn zh_chapter_preset() {
+        assert!(preset_matches("zh_chapter", "第10章"));
+        assert!(preset_matches("zh_chapter", "第一百二十回"));
+        assert!(preset_matches("zh_chapter", "第 3 节 初见"));
+        // 以 “第” 开头的正文
+        assert!(!preset_matches("zh_chapter", "第二天一早，他就出门了。"));
+        assert!(!preset_matches(
+            "zh_chapter",
+            "第一次见面时，她说这一章终于写完了"
+        ));
+    }
+
+    #[test]
+    fn zh_volume_preset() {
+        assert!(preset_matches("zh_volume", "卷三"));
+        assert!(preset_matches("zh_volume", "第二卷 风云"));
+        assert!(!preset_matches("zh_volume", "卷起袖子就开始干活。"));
+        assert!(!preset_matches("zh_volume", "第10章"));
+    }
+
+    #[test]
+    fn zh_special_preset() {
+        assert!(preset_matches("zh_special", "楔子"));
+        assert!(preset_matches("zh_special", "番外一"));
+        assert!(preset_matches("zh_special", "后记：写在最后"));
+        assert!(!preset_matches(
+            "zh_special",
+            "序幕拉开了，众人都看着台上。"
+        ));
+    }
+
+    #[test]
+    fn en_chapter_preset() {
+        assert!(preset_matches("en_chapter", "Chapter 12"));
+        assert!(preset_matches("en_chapter", "CHAPTER ONE"));
+        assert!(preset_matches("en_chapter", "Part IV"));
+        assert!(!preset_matches(
+            "en_chapter",
+            "Part of the problem was him."
+        ));
+        assert!(!preset_matches("en_chapter", "chapter and verse"));
+    }
+
+    #[test]
+    fn en_special_preset() {
+        assert!(preset_matches("en_special", "PROLOGUE"));
+        assert!(preset_matches("en_special", "Epilogue: Home"));
+        assert!(!preset_matches(
+            "en_special",
+            "Prologues are often skipped by readers."
+        ));
+    }
+
+    #[test]
+    fn numbered_preset() {
+        assert!(preset_matches("numbered", "12、大结局"));
+        assert!(preset_matches("numbered", "3. 开始"));
+        assert!(!preset_matches("numbered", "2024年的冬天格外冷。"));
+        assert!(!preset_matches("numbered", "12、"));
+        // 纯数字编号不是默认预设
+        assert!(!ChapterRulePreset::find("numbered").unwrap().is_default);
+    }
+}
@@ -0,0 +1,212 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::Paragraph;
+
+// 替换规则的类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceRuleKind {
+    // 按原文替换
+    Literal,
+    // 按正则替换，替换内容中可以使用 $1 等引用分组
+    Regex,
+    // 删除匹配正则的整个段落
+    DeleteLine,
+}
+
+impl ReplaceRuleKind {
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "literal" => Some(ReplaceRuleKind::Literal),
+            "regex" => Some(ReplaceRuleKind::Regex),
+            "delete_line" => Some(ReplaceRuleKind::DeleteLine),
+            _ => None,
+        }
+    }
+}
+
+// 参与解析的替换规则，只保留影响结果的字段
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CleanupRule {
+    pub kind: ReplaceRuleKind,
+    pub pattern: String,
+    pub replacement: String,
+}
+
+// 规则对一个段落的修改，after 为空表示段落被删除
+#[derive(Debug, Serialize, Clone)]
+pub struct CleanupPreview {
+    pub before: String,
+    pub after: Option<String>,
+}
+
+enum Step {
+    Literal(String, String),
+    Regex(Regex, String),
+    DeleteLine(Regex),
+}
+
+// 按顺序执行替换规则，清理水印、广告等内容
+pub struct Cleaner {
+    steps: Vec<Step>,
+}
+
+impl Cleaner {
+    pub fn new(rules: &[CleanupRule]) -> Result<Self, String> {
+        let steps = rules
+            .iter()
+            .map(|rule| match rule.kind {
+                ReplaceRuleKind::Literal => Ok(Step::Literal(
+                    rule.pattern.clone(),
+                    rule.replacement.clone(),
+                )),
+                ReplaceRuleKind::Regex => Ok(Step::Regex(
+                    compile(&rule.pattern)?,
+                    rule.replacement.clone(),
+                )),
+                ReplaceRuleKind::DeleteLine => Ok(Step::DeleteLine(compile(&rule.pattern)?)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { steps })
+    }
+
+    // 清理一个段落，返回空表示删除该段落
+    // 章节标题只做替换，不会被删除，替换后为空时保留原标题
+    pub fn clean(&self, paragraph: &Paragraph) -> Option<String> {
+        let mut text = paragraph.text.clone();
+
+        for step in &self.steps {
+            match step {
+                Step::Literal(pattern, replacement) => {
+                    if !pattern.is_empty() {
+                        text = text.replace(pattern, replacement);
+                    }
+                }
+                Step::Regex(regex, replacement) => {
+                    text = regex.replace_all(&text, replacement.as_str()).into_owned();
+                }
+                Step::DeleteLine(regex) => {
+                    if !paragraph.is_chapter && regex.is_match(&text) {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        let text = text.trim();
+
+        match (text.is_empty(), paragraph.is_chapter) {
+            (false, _) => Some(text.to_string()),
+            (true, true) => Some(paragraph.text.clone()),
+            (true, false) => None,
+        }
+    }
+
+    pub fn clean_paragraphs(&self, paragraphs: Vec<Paragraph>) -> Vec<Paragraph> {
+        if self.steps.is_empty() {
+            return paragraphs;
+        }
+
+        paragraphs
+            .into_iter()
+            .filter_map(|paragraph| {
+                let text = self.clean(&paragraph)?;
+                Some(Paragraph {
+                    is_chapter: paragraph.is_chapter,
+                    text,
+                })
+            })
+            .collect()
+    }
+
+    // 列出规则会修改的段落，用于预览
+    pub fn preview(&self, paragraphs: &[Paragraph]) -> Vec<CleanupPreview> {
+        paragraphs
+            .iter()
+            .filter_map(|paragraph| {
+                let after = self.clean(paragraph);
+
+                (after.as_deref() != Some(paragraph.text.as_str())).then(|| CleanupPreview {
+                    before: paragraph.text.clone(),
+                    after,
+                })
+            })
+            .collect()
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("替换规则不是合法的正则表达式: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(kind: ReplaceRuleKind, pattern: &str, replacement: &str) -> CleanupRule {
+        CleanupRule {
+            kind,
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    fn paragraph(is_chapter: bool, text: &str) -> Paragraph {
+        Paragraph {
+            is_chapter,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn literal_rule_replaces_text() {
+        let cleaner = Cleaner::new(&[rule(ReplaceRuleKind::Literal, "(本章完)", "")]).unwrap();
+
+        assert_eq!(
+            cleaner.clean(&paragraph(false, "他走了。(本章完)")),
+            Some("他走了。".to_string())
+        );
+        // 原文替换不把规则当作正则
+        assert_eq!(
+            cleaner.clean(&paragraph(false, "本章完")),
+            Some("本章完".to_string())
+        );
+        // 只剩下规则内容的段落被删除
+        assert_eq!(cleaner.clean(&paragraph(false, "(本章完)")), None);
+    }
+
+    #[test]
+    fn regex_rule_replaces_with_groups() {
+        let cleaner =
+            Cleaner::new(&[rule(ReplaceRuleKind::Regex, r"第(\d+)章\s*", "第 $1 章 ")]).unwrap();
+
+        assert_eq!(
+            cleaner.clean(&paragraph(true, "第12章风起")),
+            Some("第 12 章 风起".to_string())
+        );
+
+        let invalid = Cleaner::new(&[rule(ReplaceRuleKind::Regex, "(", "")]);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn delete_line_rule_keeps_chapter_titles() {
+        let cleaner = Cleaner::new(&[rule(ReplaceRuleKind::DeleteLine, "请收藏本站", "")]).unwrap();
+
+        let paragraphs = vec![
+            paragraph(true, "第一章 请收藏本站"),
+            paragraph(false, "天色已晚。"),
+            paragraph(false, "最新章节请收藏本站 www.example.com"),
+        ];
+
+        let texts: Vec<String> = cleaner
+            .clean_paragraphs(paragraphs)
+            .into_iter()
+            .map(|paragraph| paragraph.text)
+            .collect();
+
+        assert_eq!(texts, vec!["第一章 请收藏本站", "天色已晚。"]);
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use zhconv::{zhconv, Variant};
+
+use super::Paragraph;
+
+// 简繁转换的目标，转换使用 OpenCC 与 MediaWiki 的词组词典，按词组而非逐字转换
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChineseConversion {
+    // 不转换
+    #[default]
+    None,
+    // 转为简体
+    Simplified,
+    // 转为繁体
+    Traditional,
+    // 转为台湾正体，并转换地区用词
+    Taiwan,
+    // 转为香港繁体，并转换地区用词
+    HongKong,
+}
+
+impl ChineseConversion {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChineseConversion::None => "none",
+            ChineseConversion::Simplified => "simplified",
+            ChineseConversion::Traditional => "traditional",
+            ChineseConversion::Taiwan => "taiwan",
+            ChineseConversion::HongKong => "hong_kong",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "none" => Some(ChineseConversion::None),
+            "simplified" => Some(ChineseConversion::Simplified),
+            "traditional" => Some(ChineseConversion::Traditional),
+            "taiwan" => Some(ChineseConversion::Taiwan),
+            "hong_kong" => Some(ChineseConversion::HongKong),
+            _ => None,
+        }
+    }
+
+    fn variant(&self) -> Option<Variant> {
+        match self {
+            ChineseConversion::None => None,
+            ChineseConversion::Simplified => Some(Variant::ZhHans),
+            ChineseConversion::Traditional => Some(Variant::ZhHant),
+            ChineseConversion::Taiwan => Some(Variant::ZhTW),
+            ChineseConversion::HongKong => Some(Variant::ZhHK),
+        }
+    }
+}
+
+// 转换解析出的段落，章节标题与正文一并转换
+pub fn convert_paragraphs(paragraphs: &mut [Paragraph], conversion: ChineseConversion) {
+    let Some(variant) = conversion.variant() else {
+        return;
+    };
+
+    for paragraph in paragraphs {
+        paragraph.text = zhconv(&paragraph.text, variant);
+    }
+}
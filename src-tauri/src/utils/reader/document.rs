@@ -0,0 +1,574 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{
+    cleanup::Cleaner, convert, measure, read_paragraphs, wrap, Chapter, Line, Paragraph,
+    ReaderOptions,
+};
+
+// 解析器版本，解析结果的结构或各格式的解析逻辑变化后递增，使旧的缓存失效
+const PARSER_VERSION: u32 = 3;
+
+// 排版器版本，折行规则变化后递增，使缓存的行数失效
+const LAYOUT_VERSION: u32 = 2;
+
+// 缓存的正文中每个段落占一行，段落内的换行（如诗歌中的 <br>）以 Unicode 行分隔符保存
+const LINE_SEPARATOR: char = '\u{2028}';
+
+// 长章节按该大小拆分为多个区块，阅读时只排版阅读位置附近的区块
+const MAX_SECTION_BYTES: u64 = 256 * 1024;
+
+// 解析后的书籍，与每行字数、字体等排版设置无关
+// 正文以 UTF-8 文本保存，每个段落占一行；区块索引记录各区块在文本中的字节范围
+#[derive(Debug)]
+pub struct Document {
+    pub sections: Vec<Section>,
+    text: DocumentText,
+}
+
+// 正文中连续的一段：第一个章节之前的内容、一个章节，或长章节拆分后的一部分
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Section {
+    // 区块以章节标题开始时为章节标题
+    pub title: Option<String>,
+    // 区块正文在文本中的字节范围
+    pub start: u64,
+    pub end: u64,
+    // 段落数，不含章节标题
+    pub paragraph_count: usize,
+}
+
+// 按当前排版设置计算出的各区块行数与章节位置
+#[derive(Debug, Clone)]
+pub struct Layout {
+    // 各区块的起始行号，最后一项为总行数
+    pub section_starts: Vec<usize>,
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Debug)]
+enum DocumentText {
+    Memory(String),
+    // 缓存目录中的文本文件，按区块读取
+    File(PathBuf),
+}
+
+impl Document {
+    // 优先读取缓存，缓存不存在或已失效时重新解析文件并写入缓存
+    pub fn load(path: &str, options: &ReaderOptions) -> Result<Self, String> {
+        let Some(cache_dir) = &options.cache_dir else {
+            return Self::parse(path, options);
+        };
+
+        let cache_name = cache_name(path, options)?;
+        let index_path = cache_dir.join(format!("{cache_name}.json"));
+        let text_path = cache_dir.join(format!("{cache_name}.txt"));
+
+        if let Some(document) = Self::read_cache(&index_path, &text_path) {
+            return Ok(document);
+        }
+
+        let paragraphs = parse_paragraphs(path, options)?;
+
+        // 缓存只用于加快打开速度、减少内存占用，写入失败时仍使用内存中的文本
+        match Self::save_cache(&paragraphs, cache_dir, &index_path, &text_path) {
+            Ok(sections) => Ok(Self {
+                sections,
+                text: DocumentText::File(text_path),
+            }),
+            Err(_) => Ok(Self::from_paragraphs(&paragraphs)),
+        }
+    }
+
+    pub fn parse(path: &str, options: &ReaderOptions) -> Result<Self, String> {
+        Ok(Self::from_paragraphs(&parse_paragraphs(path, options)?))
+    }
+
+    fn from_paragraphs(paragraphs: &[Paragraph]) -> Self {
+        let mut text = Vec::new();
+
+        // 写入内存不会失败
+        let sections = write_text(paragraphs, &mut text).unwrap_or_default();
+
+        Self {
+            sections,
+            text: DocumentText::Memory(String::from_utf8_lossy(&text).into_owned()),
+        }
+    }
+
+    // 计算各区块的行数与章节位置
+    // 行数按排版设置缓存，同样的设置再次打开时不必重新排版全书
+    pub fn layout(&self, options: &ReaderOptions) -> Result<Layout, String> {
+        let cache_path = self.layout_cache_path(options);
+
+        let line_counts = match cache_path
+            .as_deref()
+            .and_then(|path| self.read_layout_cache(path))
+        {
+            Some(line_counts) => line_counts,
+            None => {
+                let line_counts = self.count_lines(options)?;
+
+                // 写入失败时下次重新排版即可
+                if let Some(path) = &cache_path {
+                    if let Ok(json) = serde_json::to_vec(&line_counts) {
+                        let _ = fs::write(path, json);
+                    }
+                }
+
+                line_counts
+            }
+        };
+
+        let mut section_starts = Vec::with_capacity(self.sections.len() + 1);
+        let mut total_lines = 0;
+
+        for line_count in line_counts {
+            section_starts.push(total_lines);
+            total_lines += line_count;
+        }
+
+        section_starts.push(total_lines);
+
+        Ok(Layout {
+            chapters: self.chapters(&section_starts),
+            section_starts,
+        })
+    }
+
+    // 行数缓存与正文缓存放在一起，文件名带上排版设置的哈希
+    fn layout_cache_path(&self, options: &ReaderOptions) -> Option<PathBuf> {
+        let DocumentText::File(text_path) = &self.text else {
+            return None;
+        };
+
+        let cache_name = text_path.file_stem()?.to_string_lossy();
+        let layout_hash = hex_digest(Sha256::digest(options.layout_key()));
+
+        Some(text_path.with_file_name(format!(
+            "{cache_name}-layout-{}-v{LAYOUT_VERSION}.json",
+            &layout_hash[..16]
+        )))
+    }
+
+    fn read_layout_cache(&self, path: &Path) -> Option<Vec<usize>> {
+        let line_counts: Vec<usize> = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+
+        // 区块数量不符时重新排版
+        (line_counts.len() == self.sections.len()).then_some(line_counts)
+    }
+
+    // 从头到尾读一遍正文，逐段折行并只记录各区块的行数，不保留排版出的行
+    fn count_lines(&self, options: &ReaderOptions) -> Result<Vec<usize>, String> {
+        let reader: Box<dyn BufRead> = match &self.text {
+            DocumentText::Memory(text) => Box::new(text.as_bytes()),
+            DocumentText::File(path) => {
+                Box::new(BufReader::new(File::open(path).map_err(|e| e.to_string())?))
+            }
+        };
+
+        let mut line_counts: Vec<usize> = self
+            .sections
+            .iter()
+            .map(|section| usize::from(section.title.is_some()))
+            .collect();
+
+        // 区块在文本中首尾相接，按字节位置找到段落所在的区块
+        let mut section_index = 0;
+        let mut position = 0;
+
+        for paragraph in reader.lines() {
+            let paragraph = paragraph.map_err(|e| e.to_string())?;
+
+            while self
+                .sections
+                .get(section_index)
+                .is_some_and(|section| section.end <= position)
+            {
+                section_index += 1;
+            }
+
+            if let Some(line_count) = line_counts.get_mut(section_index) {
+                *line_count += wrap_lines(&paragraph, options).len();
+            }
+
+            position += paragraph.len() as u64 + 1;
+        }
+
+        Ok(line_counts)
+    }
+
+    // 排版全书，返回所有行
+    pub fn lines(&self, options: &ReaderOptions) -> Result<(Vec<Line>, Vec<Chapter>), String> {
+        let mut lines: Vec<Line> = Vec::new();
+        let mut section_starts = Vec::with_capacity(self.sections.len() + 1);
+
+        for index in 0..self.sections.len() {
+            section_starts.push(lines.len());
+            lines.append(&mut self.layout_section(index, options)?);
+        }
+
+        section_starts.push(lines.len());
+
+        Ok((lines, self.chapters(&section_starts)))
+    }
+
+    // 按每行字数或窗口宽度排版一个区块
+    pub fn layout_section(
+        &self,
+        index: usize,
+        options: &ReaderOptions,
+    ) -> Result<Vec<Line>, String> {
+        let section = &self.sections[index];
+        let text = self.read_section(section)?;
+
+        let mut lines: Vec<Line> = Vec::new();
+
+        if let Some(title) = &section.title {
+            lines.push(Line {
+                is_chapter: true,
+                content: title.clone(),
+                offset: 0,
+                is_paragraph_start: true,
+                is_paragraph_end: true,
+            });
+        }
+
+        for paragraph in text.lines() {
+            layout_paragraph(&mut lines, paragraph, options);
+        }
+
+        Ok(lines)
+    }
+
+    fn chapters(&self, section_starts: &[usize]) -> Vec<Chapter> {
+        let mut chapters: Vec<Chapter> = self
+            .sections
+            .iter()
+            .enumerate()
+            .filter_map(|(index, section)| Some((section.title.clone()?, section_starts[index])))
+            .enumerate()
+            .map(|(index, (title, start_line))| Chapter {
+                index,
+                title,
+                start_line,
+            })
+            .collect();
+
+        // 没有识别出任何章节时，将全文作为一个章节，保证目录可用
+        if chapters.is_empty() {
+            chapters.push(Chapter {
+                index: 0,
+                title: "正文".to_string(),
+                start_line: 0,
+            });
+        }
+
+        chapters
+    }
+
+    // 区块中的段落，包括章节标题
+    pub fn section_paragraphs(&self, index: usize) -> Result<Vec<Paragraph>, String> {
+        let section = &self.sections[index];
+        let text = self.read_section(section)?;
+
+        let title = section.title.iter().map(|title| Paragraph {
+            is_chapter: true,
+            text: title.clone(),
+        });
+
+        let paragraphs = text.lines().map(|text| Paragraph {
+            is_chapter: false,
+            text: text.to_string(),
+        });
+
+        Ok(title.chain(paragraphs).collect())
+    }
+
+    fn read_section(&self, section: &Section) -> Result<String, String> {
+        match &self.text {
+            DocumentText::Memory(text) => {
+                Ok(text[section.start as usize..section.end as usize].to_string())
+            }
+            DocumentText::File(path) => {
+                let mut file = File::open(path).map_err(|e| e.to_string())?;
+                file.seek(SeekFrom::Start(section.start))
+                    .map_err(|e| e.to_string())?;
+
+                let mut buffer = vec![0u8; (section.end - section.start) as usize];
+                file.read_exact(&mut buffer).map_err(|e| e.to_string())?;
+
+                String::from_utf8(buffer).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    fn read_cache(index_path: &Path, text_path: &Path) -> Option<Self> {
+        let sections: Vec<Section> = serde_json::from_slice(&fs::read(index_path).ok()?).ok()?;
+
+        // 文本文件不完整时重新解析
+        let text_size = fs::metadata(text_path).ok()?.len();
+        if sections.is_empty() || sections.iter().any(|section| section.end > text_size) {
+            return None;
+        }
+
+        Some(Self {
+            sections,
+            text: DocumentText::File(text_path.to_path_buf()),
+        })
+    }
+
+    // 将段落逐个写入缓存文本，同时生成区块索引，不在内存中拼接全文
+    // 先写文本再写索引，索引存在即表示缓存完整
+    // 同一文件的旧缓存可能仍在被当前的阅读器读取，不在这里删除，见 remove_stale_cache
+    fn save_cache(
+        paragraphs: &[Paragraph],
+        cache_dir: &Path,
+        index_path: &Path,
+        text_path: &Path,
+    ) -> Result<Vec<Section>, String> {
+        fs::create_dir_all(cache_dir).map_err(|e| e.to_string())?;
+
+        let mut writer = BufWriter::new(File::create(text_path).map_err(|e| e.to_string())?);
+        let sections = write_text(paragraphs, &mut writer).map_err(|e| e.to_string())?;
+        writer.flush().map_err(|e| e.to_string())?;
+
+        let index = serde_json::to_vec(&sections).map_err(|e| e.to_string())?;
+
+        fs::write(index_path, index).map_err(|e| e.to_string())?;
+
+        Ok(sections)
+    }
+
+    // 删除同一文件的其他缓存：文件修改前、按其他章节规则或旧版本解析器生成的缓存，
+    // 以及按其他排版设置计算的行数缓存，options 为当前使用的设置
+    // 旧的阅读器按需读取缓存的文本，需要在使用本文档的阅读器替换掉旧的阅读器之后调用
+    pub fn remove_stale_cache(&self, options: &ReaderOptions) {
+        let DocumentText::File(text_path) = &self.text else {
+            return;
+        };
+
+        let layout_cache_name = self
+            .layout_cache_path(options)
+            .and_then(|path| Some(path.file_name()?.to_string_lossy().to_string()));
+
+        let (Some(cache_dir), Some(cache_name)) = (text_path.parent(), text_path.file_stem())
+        else {
+            return;
+        };
+
+        // 缓存名称以文件路径的哈希开头，当前文档的索引、文本与行数缓存都以缓存名称开头
+        let cache_name = cache_name.to_string_lossy();
+        let Some((path_hash, _)) = cache_name.split_once('-') else {
+            return;
+        };
+
+        let Ok(entries) = fs::read_dir(cache_dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            let is_stale = if name.starts_with(cache_name.as_ref()) {
+                name.contains("-layout-") && layout_cache_name.as_deref() != Some(name.as_ref())
+            } else {
+                name.starts_with(path_hash)
+            };
+
+            if is_stale {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    // 删除文件的所有缓存，小说被删除或文件被替换后调用
+    pub fn remove_cache(cache_dir: &Path, path: &str) {
+        let Ok(entries) = fs::read_dir(cache_dir) else {
+            return;
+        };
+
+        let path_hash = cache_path_hash(path);
+
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&path_hash) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+// 解析文件得到全部段落，并完成简繁转换与替换规则
+fn parse_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+    let mut paragraphs = read_paragraphs(path, options)?;
+
+    if paragraphs.is_empty() {
+        return Err("文件内容为空".to_string());
+    }
+
+    // 在排版之前转换，章节标题与缓存的正文都是转换后的文本
+    convert::convert_paragraphs(&mut paragraphs, options.conversion);
+
+    // 替换规则在章节识别与简繁转换之后执行：
+    // 规则是对照阅读器中显示的（转换后的）文本编写的，必须在转换之后执行；
+    // 而章节规则按原文编写（如预设中的 “节” 转换为繁体后是 “節”），识别只能在转换之前进行。
+    // 章节标题仍会执行替换，只是不会被删除
+    let paragraphs = Cleaner::new(&options.cleanup_rules)?.clean_paragraphs(paragraphs);
+
+    if paragraphs.is_empty() {
+        return Err("替换规则删除了全部内容".to_string());
+    }
+
+    Ok(paragraphs)
+}
+
+// 将段落逐个写入正文，每个段落占一行，同时记录各区块在正文中的字节范围
+fn write_text(paragraphs: &[Paragraph], writer: &mut impl Write) -> io::Result<Vec<Section>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut position = 0;
+
+    for paragraph in paragraphs {
+        if paragraph.is_chapter {
+            sections.push(Section {
+                title: Some(paragraph.text.clone()),
+                start: position,
+                end: position,
+                paragraph_count: 0,
+            });
+            continue;
+        }
+
+        if sections
+            .last()
+            .is_none_or(|section| section.end - section.start >= MAX_SECTION_BYTES)
+        {
+            sections.push(Section {
+                title: None,
+                start: position,
+                end: position,
+                paragraph_count: 0,
+            });
+        }
+
+        // 段落内的换行替换为行分隔符
+        let text = paragraph.text.replace('\n', &LINE_SEPARATOR.to_string());
+        writer.write_all(text.as_bytes())?;
+        writer.write_all(b"\n")?;
+        position += text.len() as u64 + 1;
+
+        if let Some(section) = sections.last_mut() {
+            section.end = position;
+            section.paragraph_count += 1;
+        }
+    }
+
+    Ok(sections)
+}
+
+// 缓存名称：文件路径的哈希，加上文件大小、修改时间与解析设置的哈希
+// 以大小和修改时间判断文件是否变化，打开时不必读取整个文件计算哈希
+fn cache_name(path: &str, options: &ReaderOptions) -> Result<String, String> {
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    // 章节规则、编码等设置不同，解析结果也不同
+    let version_hash = hex_digest(Sha256::digest(format!(
+        "{}\n{modified}\n{}",
+        metadata.len(),
+        options.parse_key()
+    )));
+
+    Ok(format!(
+        "{}-{}-v{PARSER_VERSION}",
+        cache_path_hash(path),
+        &version_hash[..16]
+    ))
+}
+
+// 同一文件的所有缓存都以文件路径的哈希开头
+fn cache_path_hash(path: &str) -> String {
+    hex_digest(Sha256::digest(path))[..16].to_string()
+}
+
+// 文件内容的 SHA-256，用于识别内容相同的文件
+pub fn file_hash(path: &str) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let size = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+
+        if size == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..size]);
+    }
+
+    Ok(hex_digest(hasher.finalize()))
+}
+
+pub fn hex_digest(digest: impl AsRef<[u8]>) -> String {
+    digest
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// 按每行宽度折行，段落内的换行处强制换行
+fn wrap_lines(paragraph: &str, options: &ReaderOptions) -> Vec<String> {
+    paragraph
+        .split(LINE_SEPARATOR)
+        .flat_map(|text| match &options.pixel_fit {
+            Some(fit) => measure::wrap_to_pixels(text, fit),
+            None => wrap::wrap_paragraph(text, options.line_size),
+        })
+        .collect()
+}
+
+// 将一个段落按每行宽度折行后追加到行列表，并记录每行在段落中的字符偏移与段落边界
+fn layout_paragraph(lines: &mut Vec<Line>, paragraph: &str, options: &ReaderOptions) {
+    let contents = wrap_lines(paragraph, options);
+
+    // 折行只会去掉行尾的空白，每行都是段落中连续的一段
+    let mut search_start = 0;
+    let mut last_position = 0;
+    let mut offset = 0;
+
+    let count = contents.len();
+
+    for (index, content) in contents.into_iter().enumerate() {
+        if let Some(position) = paragraph[search_start..].find(&content) {
+            let position = search_start + position;
+
+            offset += paragraph[last_position..position].chars().count();
+            last_position = position;
+            search_start = position + content.len();
+        }
+
+        lines.push(Line {
+            is_chapter: false,
+            content,
+            offset,
+            is_paragraph_start: index == 0,
+            is_paragraph_end: index + 1 == count,
+        });
+    }
+}
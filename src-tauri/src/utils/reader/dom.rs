@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+
+use scraper::{ElementRef, Html};
+
+use super::{chapter::detect_chapter_lines, push_paragraph, Paragraph};
+
+// 不包含正文的元素
+const IGNORED_ELEMENTS: &[&str] = &[
+    "head", "title", "script", "style", "noscript", "template", "svg", "math", "rt", "rp",
+];
+
+// 块级元素，前后都是段落边界
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "caption",
+    "center",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "header",
+    "hgroup",
+    "hr",
+    "li",
+    "main",
+    // MOBI 的分页标记
+    "mbp:pagebreak",
+    "nav",
+    "ol",
+    "p",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Block {
+    // 指定 id 的元素出现的位置
+    Anchor(String),
+    Heading { level: u8, text: String },
+    Paragraph(String),
+}
+
+// 按文档顺序遍历 DOM，将正文切分为标题与段落；anchors 中的 id 会作为锚点输出
+pub fn walk_blocks(html: &Html, anchors: &HashSet<&str>) -> Vec<Block> {
+    let mut walker = BlockWalker {
+        anchors,
+        blocks: Vec::new(),
+        text: String::new(),
+        preformatted: 0,
+    };
+
+    walker.walk(html.root_element());
+    walker.flush_paragraph();
+
+    walker.blocks
+}
+
+// 将标题与段落转换为行：不高于 chapter_level 级的标题作为章节，
+// 文档中没有这类标题时，与 TXT 一样按章节规则识别
+pub fn blocks_to_paragraphs(
+    blocks: Vec<Block>,
+    chapter_level: u8,
+    chapter_patterns: &[String],
+) -> Result<Vec<Paragraph>, String> {
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+
+    let has_headings = blocks
+        .iter()
+        .any(|block| matches!(block, Block::Heading { level, .. } if *level <= chapter_level));
+
+    if has_headings {
+        for block in blocks {
+            match block {
+                Block::Heading { level, text } if level <= chapter_level => {
+                    paragraphs.push(Paragraph {
+                        is_chapter: true,
+                        text,
+                    })
+                }
+                Block::Heading { text, .. } | Block::Paragraph(text) => {
+                    push_paragraph(&mut paragraphs, &text)
+                }
+                Block::Anchor(_) => {}
+            }
+        }
+
+        return Ok(paragraphs);
+    }
+
+    let texts: Vec<String> = blocks
+        .into_iter()
+        .filter_map(|block| match block {
+            Block::Heading { text, .. } | Block::Paragraph(text) => Some(text),
+            Block::Anchor(_) => None,
+        })
+        .collect();
+    let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+    let is_chapter_line = detect_chapter_lines(&texts, chapter_patterns)?;
+
+    for (index, text) in texts.into_iter().enumerate() {
+        if is_chapter_line[index] {
+            paragraphs.push(Paragraph {
+                is_chapter: true,
+                text: text.to_string(),
+            });
+        } else {
+            push_paragraph(&mut paragraphs, text);
+        }
+    }
+
+    Ok(paragraphs)
+}
+
+// 忽略空白比较两个标题是否相同
+pub fn is_same_title(a: &str, b: &str) -> bool {
+    a.chars()
+        .filter(|c| !c.is_whitespace())
+        .eq(b.chars().filter(|c| !c.is_whitespace()))
+}
+
+struct BlockWalker<'a> {
+    anchors: &'a HashSet<&'a str>,
+    blocks: Vec<Block>,
+    // 当前段落中尚未输出的文本
+    text: String,
+    // 位于 <pre> 内的层数
+    preformatted: usize,
+}
+
+impl BlockWalker<'_> {
+    fn walk(&mut self, element: ElementRef) {
+        let name = element.value().name();
+
+        if IGNORED_ELEMENTS.contains(&name) {
+            return;
+        }
+
+        if let Some(id) = element.value().id().filter(|id| self.anchors.contains(id)) {
+            self.flush_paragraph();
+            self.blocks.push(Block::Anchor(id.to_string()));
+        }
+
+        if let Some(level) = heading_level(name) {
+            self.flush_paragraph();
+            self.walk_children(element);
+            // 标题只占一行，其中的换行替换为空格
+            let text = self.take_text().replace('\n', " ");
+            if !text.is_empty() {
+                self.blocks.push(Block::Heading { level, text });
+            }
+        } else if name == "br" {
+            // 诗歌等以 <br> 分行的内容仍属于同一段落，在段落内换行，连续的 <br> 只换一行
+            self.text.truncate(self.text.trim_end().len());
+            if !self.text.is_empty() {
+                self.text.push('\n');
+            }
+        } else if name == "pre" {
+            self.flush_paragraph();
+            self.preformatted += 1;
+            self.walk_children(element);
+            self.preformatted -= 1;
+            self.flush_paragraph();
+        } else if BLOCK_ELEMENTS.contains(&name) {
+            self.flush_paragraph();
+            self.walk_children(element);
+            self.flush_paragraph();
+        } else {
+            self.walk_children(element);
+        }
+    }
+
+    fn walk_children(&mut self, element: ElementRef) {
+        for child in element.children() {
+            if let Some(child_element) = ElementRef::wrap(child) {
+                self.walk(child_element);
+            } else if let Some(text) = child.value().as_text() {
+                self.push_text(text);
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.preformatted > 0 {
+            let mut segments = text.split('\n');
+
+            if let Some(first) = segments.next() {
+                self.text.push_str(first);
+            }
+
+            for segment in segments {
+                self.flush_paragraph();
+                self.text.push_str(segment);
+            }
+
+            return;
+        }
+
+        // 折叠连续的空白，与浏览器的渲染保持一致
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                if !self.text.is_empty() && !self.text.ends_with([' ', '\n']) {
+                    self.text.push(' ');
+                }
+            } else {
+                self.text.push(c);
+            }
+        }
+    }
+
+    fn take_text(&mut self) -> String {
+        let text = self.text.trim().to_string();
+        self.text.clear();
+        text
+    }
+
+    fn flush_paragraph(&mut self) {
+        let text = self.take_text();
+
+        if !text.is_empty() {
+            self.blocks.push(Block::Paragraph(text));
+        }
+    }
+}
+
+fn heading_level(name: &str) -> Option<u8> {
+    match name {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(html: &str) -> Vec<Block> {
+        walk_blocks(&Html::parse_document(html), &HashSet::new())
+    }
+
+    fn paragraph(text: &str) -> Block {
+        Block::Paragraph(text.to_string())
+    }
+
+    #[test]
+    fn splits_text_at_block_elements() {
+        let html = "<div>第一段<p>第二段</p>第三段</div><ul><li>其一</li><li>其<b>二</b></li></ul>";
+
+        assert_eq!(
+            blocks(html),
+            vec![
+                paragraph("第一段"),
+                paragraph("第二段"),
+                paragraph("第三段"),
+                paragraph("其一"),
+                paragraph("其二"),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_script_and_style() {
+        let html = "<html><head><title>书名</title><style>p { color: red; }</style></head>\
+                    <body><p>正文<script>document.write('广告');</script>继续</p></body></html>";
+
+        assert_eq!(blocks(html), vec![paragraph("正文继续")]);
+    }
+
+    #[test]
+    fn breaks_lines_within_paragraph_at_br() {
+        let html = "<h2>静夜思<br/>李白</h2>\
+                    <p>床前明月光， <br/> 疑是地上霜。<br/><br/>举头望明月，<br/></p><p>下一段</p>";
+
+        assert_eq!(
+            blocks(html),
+            vec![
+                Block::Heading {
+                    level: 2,
+                    text: "静夜思 李白".to_string(),
+                },
+                paragraph("床前明月光，\n疑是地上霜。\n举头望明月，"),
+                paragraph("下一段"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::BufReader,
+    path::{Component, Path, PathBuf},
+};
+
+use epub::doc::{EpubDoc, NavPoint};
+use scraper::{ElementRef, Html, Selector};
+
+use super::{
+    dom::{is_same_title, walk_blocks, Block},
+    push_paragraph, FileReader, NovelMetadata, Paragraph, ReaderOptions,
+};
+
+// 目录中的一项，指向某个文件（及其中的锚点）
+struct TocEntry {
+    title: String,
+    path: String,
+    fragment: Option<String>,
+}
+
+pub struct EpubReader;
+
+impl FileReader for EpubReader {
+    fn read_paragraphs(path: &str, _options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let mut doc = EpubDoc::new(path).map_err(|e| e.to_string())?;
+
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+
+        let toc = Self::read_toc(&mut doc);
+
+        // 书脊中的文件路径，及用于与目录项比较的规范化路径
+        let spine_paths: Vec<(PathBuf, String)> = doc
+            .spine
+            .iter()
+            .filter_map(|spine| doc.resources.get(&spine.idref))
+            .map(|(page_path, _)| {
+                let key = normalize_path(Path::new(&percent_decode(&page_path.to_string_lossy())));
+                (page_path.clone(), key)
+            })
+            .collect();
+
+        // 只保留指向书脊中文件的目录项，按文件分组
+        let mut toc_by_path: HashMap<&str, Vec<&TocEntry>> = HashMap::new();
+        for entry in toc.iter() {
+            if spine_paths.iter().any(|(_, key)| *key == entry.path) {
+                toc_by_path.entry(&entry.path).or_default().push(entry);
+            }
+        }
+
+        let has_toc = !toc_by_path.is_empty();
+
+        for (spine_index, (page_path, page_key)) in spine_paths.iter().enumerate() {
+            let Some(page_content) = doc.get_resource_str_by_path(page_path) else {
+                continue;
+            };
+            let page_html = Html::parse_document(page_content.as_str());
+
+            let mut page_paragraphs: Vec<Paragraph> = Vec::new();
+
+            if has_toc {
+                let entries = toc_by_path.remove(page_key.as_str()).unwrap_or_default();
+
+                let ids = collect_ids(&page_html);
+
+                // 没有锚点或锚点不存在的目录项，从文件开头开始
+                let (anchored, unanchored): (Vec<&TocEntry>, Vec<&TocEntry>) =
+                    entries.into_iter().partition(|entry| {
+                        entry
+                            .fragment
+                            .as_ref()
+                            .is_some_and(|fragment| ids.contains(fragment.as_str()))
+                    });
+
+                let mut last_title = None;
+
+                for entry in unanchored {
+                    page_paragraphs.push(Paragraph {
+                        is_chapter: true,
+                        text: entry.title.clone(),
+                    });
+                    last_title = Some(entry.title.clone());
+                }
+
+                Self::read_page(&page_html, &anchored, last_title, &mut page_paragraphs);
+            } else {
+                // 没有可用的目录时，每个文件作为一章
+                let title =
+                    page_title(&page_html).unwrap_or_else(|| format!("第{}节", spine_index + 1));
+
+                Self::read_page(&page_html, &[], Some(title.clone()), &mut page_paragraphs);
+
+                if page_paragraphs.is_empty() {
+                    continue;
+                }
+
+                paragraphs.push(Paragraph {
+                    is_chapter: true,
+                    text: title,
+                });
+            }
+
+            paragraphs.append(&mut page_paragraphs);
+        }
+
+        Ok(paragraphs)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        let mut doc = EpubDoc::new(path).map_err(|e| e.to_string())?;
+
+        // dc:title 为空时返回空值，导入时使用文件名
+        let title = doc
+            .mdata("title")
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty());
+
+        Ok(NovelMetadata {
+            title,
+            author: doc.mdata("creator"),
+            description: doc.mdata("description"),
+            cover: doc.get_cover().map(|(blob, _)| blob),
+            ..Default::default()
+        })
+    }
+}
+
+impl EpubReader {
+    // 优先读取 EPUB3 的导航文档，其次读取 EPUB2 的 NCX
+    fn read_toc(doc: &mut EpubDoc<BufReader<File>>) -> Vec<TocEntry> {
+        let nav_toc = Self::read_nav_toc(doc);
+
+        if !nav_toc.is_empty() {
+            return nav_toc;
+        }
+
+        let mut toc = Vec::new();
+        flatten_nav_points(&doc.toc, &mut toc);
+        toc
+    }
+
+    fn read_nav_toc(doc: &mut EpubDoc<BufReader<File>>) -> Vec<TocEntry> {
+        let mut toc = Vec::new();
+
+        let root_file = doc.root_file.clone();
+        let Some(opf_content) = doc.get_resource_str_by_path(&root_file) else {
+            return toc;
+        };
+        let opf = Html::parse_document(&opf_content);
+
+        let item_selector = Selector::parse("item").unwrap();
+        let Some(nav_href) = opf
+            .select(&item_selector)
+            .find(|item| {
+                item.value()
+                    .attr("properties")
+                    .is_some_and(|properties| properties.split_whitespace().any(|p| p == "nav"))
+            })
+            .and_then(|item| item.value().attr("href"))
+        else {
+            return toc;
+        };
+
+        let nav_path = normalize_path(&doc.root_base.join(percent_decode(nav_href)));
+        let Some(nav_content) = doc.get_resource_str_by_path(&nav_path) else {
+            return toc;
+        };
+        let nav_html = Html::parse_document(&nav_content);
+        let nav_dir = Path::new(&nav_path).parent().unwrap_or(Path::new(""));
+
+        let nav_selector = Selector::parse("nav").unwrap();
+        let toc_nav = nav_html
+            .select(&nav_selector)
+            .find(|nav| nav.value().attr("epub:type") == Some("toc"))
+            .or_else(|| nav_html.select(&nav_selector).next());
+
+        let Some(toc_nav) = toc_nav else {
+            return toc;
+        };
+
+        let link_selector = Selector::parse("a[href]").unwrap();
+        for link in toc_nav.select(&link_selector) {
+            let title = element_text(&link);
+            let href = link.value().attr("href").unwrap_or_default();
+
+            if title.is_empty() || href.is_empty() {
+                continue;
+            }
+
+            toc.push(TocEntry::new(title, nav_dir, href));
+        }
+
+        toc
+    }
+
+    // 按文档顺序读取正文，遇到目录项指向的锚点时插入章节标题
+    fn read_page(
+        page_html: &Html,
+        anchored: &[&TocEntry],
+        mut last_title: Option<String>,
+        paragraphs: &mut Vec<Paragraph>,
+    ) {
+        let anchors: HashSet<&str> = anchored
+            .iter()
+            .filter_map(|entry| entry.fragment.as_deref())
+            .collect();
+
+        for block in walk_blocks(page_html, &anchors) {
+            match block {
+                Block::Anchor(id) => {
+                    for entry in anchored
+                        .iter()
+                        .filter(|entry| entry.fragment.as_deref() == Some(id.as_str()))
+                    {
+                        paragraphs.push(Paragraph {
+                            is_chapter: true,
+                            text: entry.title.clone(),
+                        });
+                        last_title = Some(entry.title.clone());
+                    }
+                }
+                Block::Heading { text, .. } => {
+                    // 紧跟在章节标题之后、内容相同的标题元素不再重复显示
+                    if last_title
+                        .take()
+                        .is_some_and(|title| is_same_title(&title, &text))
+                    {
+                        continue;
+                    }
+
+                    push_paragraph(paragraphs, &text);
+                }
+                Block::Paragraph(text) => {
+                    last_title = None;
+                    push_paragraph(paragraphs, &text);
+                }
+            }
+        }
+    }
+}
+
+impl TocEntry {
+    fn new(title: String, base_dir: &Path, href: &str) -> Self {
+        let (href_path, fragment) = match href.split_once('#') {
+            Some((href_path, fragment)) => (href_path, Some(percent_decode(fragment))),
+            None => (href, None),
+        };
+
+        Self {
+            title,
+            path: normalize_path(&base_dir.join(percent_decode(href_path))),
+            fragment: fragment.filter(|fragment| !fragment.is_empty()),
+        }
+    }
+}
+
+fn flatten_nav_points(nav_points: &[NavPoint], toc: &mut Vec<TocEntry>) {
+    for nav_point in nav_points {
+        let title = nav_point.label.trim().to_string();
+
+        if !title.is_empty() {
+            // NCX 中的路径已拼接了根目录
+            toc.push(TocEntry::new(
+                title,
+                Path::new(""),
+                &nav_point.content.to_string_lossy(),
+            ));
+        }
+
+        flatten_nav_points(&nav_point.children, toc);
+    }
+}
+
+fn collect_ids(html: &Html) -> HashSet<&str> {
+    html.root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter_map(|element| element.value().id())
+        .collect()
+}
+
+// 文件没有目录项时的章节标题：优先使用 <title>，其次使用第一个标题元素
+fn page_title(html: &Html) -> Option<String> {
+    let selector = Selector::parse("head > title, h1, h2, h3").unwrap();
+
+    html.select(&selector)
+        .map(|element| element_text(&element))
+        .find(|title| !title.is_empty())
+}
+
+fn element_text(element: &ElementRef) -> String {
+    element
+        .text()
+        .collect::<Vec<_>>()
+        .join("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// 统一路径分隔符并消除 “.” 与 “..”，便于比较目录项与书脊中的路径
+fn normalize_path(path: &Path) -> String {
+    let mut components: Vec<String> = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::Normal(name) => components.push(name.to_string_lossy().to_string()),
+            _ => {}
+        }
+    }
+
+    components.join("/").replace('\\', "/")
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            let high = (bytes[index + 1] as char).to_digit(16);
+            let low = (bytes[index + 2] as char).to_digit(16);
+
+            if let (Some(high), Some(low)) = (high, low) {
+                decoded.push((high * 16 + low) as u8);
+                index += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
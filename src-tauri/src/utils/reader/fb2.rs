@@ -0,0 +1,310 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use encoding::{
+    all::{UTF_16BE, UTF_16LE, UTF_8},
+    label::encoding_from_whatwg_label,
+    types::DecoderTrap,
+    EncodingRef,
+};
+use regex::Regex;
+use xml::reader::{ParserConfig, XmlEvent};
+use zip::ZipArchive;
+
+use super::{dom::Block, push_paragraph, FileReader, NovelMetadata, Paragraph, ReaderOptions};
+
+// 段落类元素，其中的文本作为一个段落
+const PARAGRAPH_ELEMENTS: &[&str] = &["p", "v", "subtitle", "text-author", "td", "th"];
+
+// 书籍信息中只包含文本的字段
+const TEXT_FIELDS: &[&str] = &[
+    "book-title",
+    "first-name",
+    "middle-name",
+    "last-name",
+    "nickname",
+    "binary",
+];
+
+// 不属于正文的 body（注释、评论等）
+const IGNORED_BODIES: &[&str] = &["notes", "comments"];
+
+pub struct Fb2Reader;
+
+impl FileReader for Fb2Reader {
+    fn read_paragraphs(path: &str, _options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let book = Fb2Book::open(path)?;
+
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+
+        for block in book.blocks {
+            match block {
+                Block::Heading { text, .. } => paragraphs.push(Paragraph {
+                    is_chapter: true,
+                    text,
+                }),
+                Block::Paragraph(text) => push_paragraph(&mut paragraphs, &text),
+                Block::Anchor(_) => {}
+            }
+        }
+
+        Ok(paragraphs)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        Ok(Fb2Book::open(path)?.metadata)
+    }
+}
+
+struct Fb2Book {
+    blocks: Vec<Block>,
+    metadata: NovelMetadata,
+}
+
+impl Fb2Book {
+    fn open(path: &str) -> Result<Self, String> {
+        let content = decode_xml(&read_fb2_file(path)?);
+
+        let reader = ParserConfig::new()
+            .cdata_to_characters(true)
+            .whitespace_to_characters(true)
+            .coalesce_characters(true)
+            .ignore_comments(true)
+            .replace_unknown_entity_references(true)
+            .add_entity("nbsp", "\u{a0}")
+            .create_reader(content.as_bytes());
+
+        let mut book = Self {
+            blocks: Vec::new(),
+            metadata: NovelMetadata::default(),
+        };
+
+        // 当前所在的元素
+        let mut stack: Vec<String> = Vec::new();
+        // 当前元素中尚未处理的文本
+        let mut text = String::new();
+        // 忽略的 body 所在的层级
+        let mut ignored_depth: Option<usize> = None;
+        let mut title_parts: Vec<String> = Vec::new();
+        let mut author_parts: Vec<String> = Vec::new();
+        let mut nickname: Option<String> = None;
+        let mut authors: Vec<String> = Vec::new();
+        let mut annotation: Vec<String> = Vec::new();
+        let mut cover_id: Option<String> = None;
+        let mut binary_id: Option<String> = None;
+
+        for event in reader {
+            let event = event.map_err(|e| format!("FB2 文件解析失败: {e}"))?;
+
+            match event {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let attribute = |key: &str| {
+                        attributes
+                            .iter()
+                            .find(|attribute| attribute.name.local_name == key)
+                            .map(|attribute| attribute.value.clone())
+                    };
+
+                    match name.local_name.as_str() {
+                        "body"
+                            if ignored_depth.is_none()
+                                && attribute("name").is_some_and(|name| {
+                                    IGNORED_BODIES.contains(&name.as_str())
+                                }) =>
+                        {
+                            ignored_depth = Some(stack.len());
+                        }
+                        "title" => title_parts.clear(),
+                        "author" => {
+                            author_parts.clear();
+                            nickname = None;
+                        }
+                        "image"
+                            if cover_id.is_none()
+                                && stack.iter().any(|name| name == "coverpage") =>
+                        {
+                            cover_id = attribute("href")
+                                .map(|href| href.trim_start_matches('#').to_string());
+                        }
+                        "binary" => binary_id = attribute("id"),
+                        _ => {}
+                    }
+
+                    // 段落中的行内元素（如 <emphasis>）不影响已读取的文本
+                    if PARAGRAPH_ELEMENTS.contains(&name.local_name.as_str())
+                        || TEXT_FIELDS.contains(&name.local_name.as_str())
+                    {
+                        text.clear();
+                    }
+
+                    stack.push(name.local_name);
+                }
+                XmlEvent::EndElement { .. } => {
+                    let Some(name) = stack.pop() else {
+                        continue;
+                    };
+
+                    if ignored_depth == Some(stack.len()) {
+                        ignored_depth = None;
+                        continue;
+                    }
+
+                    if ignored_depth.is_some() {
+                        continue;
+                    }
+
+                    let in_title_info = stack.iter().any(|name| name == "title-info");
+                    let in_body = stack.iter().any(|name| name == "body");
+
+                    match name.as_str() {
+                        "book-title" if in_title_info => {
+                            book.metadata.title =
+                                Some(collapse_whitespace(&text)).filter(|title| !title.is_empty());
+                        }
+                        "first-name" | "middle-name" | "last-name" if in_title_info => {
+                            author_parts.push(collapse_whitespace(&text));
+                        }
+                        "nickname" if in_title_info => {
+                            nickname = Some(collapse_whitespace(&text));
+                        }
+                        "author" if in_title_info => {
+                            let author = author_parts
+                                .iter()
+                                .filter(|part| !part.is_empty())
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(" ");
+
+                            match (author.is_empty(), nickname.take()) {
+                                (false, _) => authors.push(author),
+                                (true, Some(nickname)) if !nickname.is_empty() => {
+                                    authors.push(nickname)
+                                }
+                                _ => {}
+                            }
+                        }
+                        "binary" if binary_id.is_some() && binary_id == cover_id => {
+                            let data: String =
+                                text.chars().filter(|c| !c.is_whitespace()).collect();
+                            book.metadata.cover = STANDARD.decode(data).ok();
+                        }
+                        // 标题中可以有多个段落，合并为一行
+                        "title" if in_body => {
+                            let title = title_parts.join(" ");
+                            let section_depth =
+                                stack.iter().filter(|name| *name == "section").count();
+
+                            if title.is_empty() {
+                                continue;
+                            }
+
+                            if section_depth > 0 {
+                                book.blocks.push(Block::Heading {
+                                    level: section_depth.min(6) as u8,
+                                    text: title,
+                                });
+                            } else {
+                                // body 的标题通常是书名，作为普通段落显示
+                                book.blocks.push(Block::Paragraph(title));
+                            }
+                        }
+                        name if PARAGRAPH_ELEMENTS.contains(&name) => {
+                            let paragraph = collapse_whitespace(&text);
+
+                            if paragraph.is_empty() {
+                                continue;
+                            }
+
+                            if in_title_info {
+                                if stack.iter().any(|name| name == "annotation") {
+                                    annotation.push(paragraph);
+                                }
+                            } else if stack.iter().any(|name| name == "title") {
+                                title_parts.push(paragraph);
+                            } else if in_body {
+                                book.blocks.push(Block::Paragraph(paragraph));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                XmlEvent::Characters(characters) => text.push_str(&characters),
+                _ => {}
+            }
+        }
+
+        if !authors.is_empty() {
+            book.metadata.author = Some(authors.join(", "));
+        }
+
+        if !annotation.is_empty() {
+            book.metadata.description = Some(annotation.join("\n"));
+        }
+
+        Ok(book)
+    }
+}
+
+// 读取 FB2 文件内容，.fb2.zip 则读取压缩包中的第一个 FB2 文件
+fn read_fb2_file(path: &str) -> Result<Vec<u8>, String> {
+    if !path.to_lowercase().ends_with(".zip") {
+        return fs::read(path).map_err(|e| e.to_string());
+    }
+
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let name = archive
+        .file_names()
+        .find(|name| name.to_lowercase().ends_with(".fb2"))
+        .map(str::to_string)
+        .ok_or("压缩包中没有 FB2 文件")?;
+
+    let mut entry = archive.by_name(&name).map_err(|e| e.to_string())?;
+    let mut buffer = Vec::new();
+    entry.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
+
+    Ok(buffer)
+}
+
+// 按 BOM 或 XML 声明中的编码解码，并移除 XML 声明
+fn decode_xml(bytes: &[u8]) -> String {
+    let (encoding, bytes): (EncodingRef, &[u8]) = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => (UTF_8, rest),
+        [0xFF, 0xFE, rest @ ..] => (UTF_16LE, rest),
+        [0xFE, 0xFF, rest @ ..] => (UTF_16BE, rest),
+        _ => {
+            let head = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+            let declared =
+                Regex::new(r#"^\s*<\?xml[^>]*encoding\s*=\s*["']([A-Za-z0-9._:-]+)["']"#)
+                    .unwrap()
+                    .captures(&head)
+                    .and_then(|captures| encoding_from_whatwg_label(&captures[1]));
+
+            (declared.unwrap_or(UTF_8), bytes)
+        }
+    };
+
+    let content = encoding
+        .decode(bytes, DecoderTrap::Replace)
+        .unwrap_or_default();
+
+    let trimmed = content.trim_start();
+    match trimmed
+        .starts_with("<?xml")
+        .then(|| trimmed.find("?>"))
+        .flatten()
+    {
+        Some(end) => trimmed[end + 2..].to_string(),
+        None => content,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+// 标题的最大字数
+const MAX_TITLE_LENGTH: usize = 30;
+
+// 章节之间平均至少间隔的非空行数，低于该值说明误判了大量短句
+const MIN_CHAPTER_GAP: usize = 8;
+
+// 句末标点，以此结尾的行通常是正文
+const SENTENCE_ENDINGS: &[char] = &[
+    '。', '，', ',', '；', ';', '：', ':', '”', '」', '』', '"', '…', '、',
+];
+
+// 引号开头的行通常是对话
+const QUOTE_STARTS: &[char] = &['“', '「', '『', '"', '\'', '‘'];
+
+// 在没有任何章节规则命中时，根据行的统计特征推测章节标题所在的行号
+pub fn detect_chapter_lines(lines: &[&str]) -> Vec<usize> {
+    let non_empty_count = lines.iter().filter(|line| !line.is_empty()).count();
+
+    if non_empty_count == 0 {
+        return Vec::new();
+    }
+
+    let candidates: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_title_like(line))
+        .map(|(index, _)| index)
+        .collect();
+
+    if candidates.len() < 2 {
+        return Vec::new();
+    }
+
+    // 空行隔开的行占比过高时（如每段之间都有空行），“独占一行”不再有区分度
+    let isolated_count = (0..lines.len())
+        .filter(|&index| !lines[index].is_empty() && is_isolated(lines, index))
+        .count();
+    let isolation_is_meaningful = isolated_count * 2 < non_empty_count;
+
+    // 统计候选行的首字，多次出现的相同前缀（如 “卷”、“【”）更可能是标题格式
+    let mut prefix_counts: HashMap<char, usize> = HashMap::new();
+    for &index in &candidates {
+        if let Some(first_char) = lines[index].chars().next() {
+            *prefix_counts.entry(first_char).or_default() += 1;
+        }
+    }
+
+    let median_length = median_line_length(lines);
+
+    // 编号开头，如 “1”、“001 标题”、“一、标题”、“（三）标题”、“【12】标题”
+    let numbering_regex = Regex::new(
+        r"^[(（【\[]?[0-9０-９零〇一二两三四五六七八九十百千万]+[)）】\]]?(?:$|[、.．:：\s　])",
+    )
+    .unwrap();
+
+    let scores: Vec<(usize, usize)> = candidates
+        .iter()
+        .map(|&index| {
+            let line = lines[index];
+            let mut score = 0;
+
+            if numbering_regex.is_match(line) {
+                score += 2;
+            }
+
+            if isolation_is_meaningful && is_isolated(lines, index) {
+                score += 1;
+            }
+
+            if line
+                .chars()
+                .next()
+                .and_then(|first_char| prefix_counts.get(&first_char))
+                .is_some_and(|&count| count >= 3)
+            {
+                score += 1;
+            }
+
+            if line.chars().count() * 3 <= median_length {
+                score += 1;
+            }
+
+            (index, score)
+        })
+        .collect();
+
+    // 从宽松到严格尝试阈值，取第一个章节密度合理的结果
+    for threshold in 3..=5 {
+        let chapter_lines: Vec<usize> = scores
+            .iter()
+            .filter(|(_, score)| *score >= threshold)
+            .map(|(index, _)| *index)
+            .collect();
+
+        if chapter_lines.len() >= 2 && chapter_lines.len() * MIN_CHAPTER_GAP <= non_empty_count {
+            return chapter_lines;
+        }
+    }
+
+    Vec::new()
+}
+
+fn is_title_like(line: &str) -> bool {
+    let length = line.chars().count();
+
+    if length == 0 || length > MAX_TITLE_LENGTH {
+        return false;
+    }
+
+    if line.starts_with(QUOTE_STARTS) {
+        return false;
+    }
+
+    !line.ends_with(SENTENCE_ENDINGS)
+}
+
+fn is_isolated(lines: &[&str], index: usize) -> bool {
+    let prev_is_blank = index == 0 || lines[index - 1].is_empty();
+    let next_is_blank = index + 1 >= lines.len() || lines[index + 1].is_empty();
+
+    prev_is_blank && next_is_blank
+}
+
+fn median_line_length(lines: &[&str]) -> usize {
+    let mut lengths: Vec<usize> = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().count())
+        .collect();
+
+    lengths.sort_unstable();
+
+    lengths.get(lengths.len() / 2).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOVEL: &str = "
+1 出发
+
+天刚亮，他就背起行囊出了门。
+村口的老槐树下已经站满了送行的人。
+母亲把一包干粮塞进他的怀里，什么也没说。
+他回头看了一眼，便大步向山外走去。
+山路很长，一直走到中午才看见官道。
+官道上车马往来，尘土飞扬。
+他找了一处树荫坐下，打开了那包干粮。
+
+2 进城
+
+傍晚时分，城墙终于出现在眼前。
+城门口排着长长的队伍，守卫挨个查看路引。
+他掏出里正写的文书，守卫看了看便放他进去。
+城里比他想象的还要热闹，街上到处都是灯火。
+他在一家小客栈要了最便宜的房间。
+掌柜问他来城里做什么，他说来找一位故人。
+夜里他躺在床上，很久都没有睡着。
+";
+
+    #[test]
+    fn detects_numbered_isolated_titles() {
+        let lines: Vec<&str> = NOVEL.lines().collect();
+
+        assert_eq!(detect_chapter_lines(&lines), [1, 11]);
+    }
+
+    #[test]
+    fn ignores_text_without_titles() {
+        let lines: Vec<&str> = NOVEL
+            .lines()
+            .filter(|line| !line.starts_with(['1', '2']))
+            .collect();
+
+        assert!(detect_chapter_lines(&lines).is_empty());
+        assert!(detect_chapter_lines(&[]).is_empty());
+    }
+
+    #[test]
+    fn rejects_dense_short_lines() {
+        // 对话中的短句每隔一行就像一个标题，章节过密，视为误判
+        let lines = [
+            "1 号",
+            "他问：你住在哪一间房？",
+            "2 号",
+            "她答：就在你隔壁那一间。",
+            "3 号",
+            "他又问：明天一早还走吗？",
+        ];
+
+        assert!(detect_chapter_lines(&lines).is_empty());
+    }
+}
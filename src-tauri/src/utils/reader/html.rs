@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use scraper::{Html, Selector};
+
+use super::{
+    dom::{blocks_to_paragraphs, walk_blocks},
+    txt::read_text,
+    FileReader, NovelMetadata, Paragraph, ReaderOptions,
+};
+
+pub struct HtmlReader;
+
+impl FileReader for HtmlReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let html = Html::parse_document(&read_text(path, options.encoding.as_deref())?);
+        let blocks = walk_blocks(&html, &HashSet::new());
+
+        // 以一到三级标题作为章节
+        blocks_to_paragraphs(blocks, 3, &options.chapter_patterns)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        let html = Html::parse_document(&read_text(path, None)?);
+
+        let title_selector = Selector::parse("head > title").unwrap();
+        let title = html
+            .select(&title_selector)
+            .next()
+            .map(|title| title.text().collect::<String>().trim().to_string())
+            .filter(|title| !title.is_empty());
+
+        let meta_content = |name: &str| {
+            let selector = Selector::parse(&format!("meta[name=\"{name}\"]")).unwrap();
+            html.select(&selector)
+                .filter_map(|meta| meta.value().attr("content"))
+                .map(|content| content.trim().to_string())
+                .find(|content| !content.is_empty())
+        };
+
+        Ok(NovelMetadata {
+            title,
+            author: meta_content("author"),
+            description: meta_content("description"),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use pulldown_cmark::{Event, MetadataBlockKind, Options, Parser, Tag, TagEnd};
+
+use super::{
+    dom::{blocks_to_paragraphs, Block},
+    txt::read_text,
+    FileReader, NovelMetadata, Paragraph, ReaderOptions,
+};
+
+pub struct MarkdownReader;
+
+impl FileReader for MarkdownReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let (blocks, _) = parse_markdown(&read_text(path, options.encoding.as_deref())?);
+
+        // 以一、二级标题作为章节
+        blocks_to_paragraphs(blocks, 2, &options.chapter_patterns)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        let (_, front_matter) = parse_markdown(&read_text(path, None)?);
+
+        // 只读取 YAML 头信息中简单的 “键: 值” 字段
+        let field = |key: &str| {
+            front_matter.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+                (name.trim() == key && !value.is_empty()).then(|| value.to_string())
+            })
+        };
+
+        Ok(NovelMetadata {
+            title: field("title"),
+            author: field("author"),
+            description: field("description"),
+            ..Default::default()
+        })
+    }
+}
+
+// 解析 Markdown 为标题与段落，同时返回 YAML 头信息
+fn parse_markdown(content: &str) -> (Vec<Block>, String) {
+    let parser = Parser::new_ext(
+        content,
+        Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS,
+    );
+
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut front_matter = String::new();
+    // 当前段落中尚未输出的文本
+    let mut text = String::new();
+    let mut in_code_block = false;
+    let mut in_metadata = false;
+    // 位于图片中的层数，图片的替代文本不显示
+    let mut image_depth = 0;
+
+    let flush = |text: &mut String, blocks: &mut Vec<Block>| {
+        let paragraph = text.trim().to_string();
+        text.clear();
+
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph));
+        }
+    };
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::Heading { .. }) => flush(&mut text, &mut blocks),
+            Event::End(TagEnd::Heading(level)) => {
+                let heading = text.trim().to_string();
+                text.clear();
+
+                if !heading.is_empty() {
+                    blocks.push(Block::Heading {
+                        level: level as u8,
+                        text: heading,
+                    });
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut text, &mut blocks);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                flush(&mut text, &mut blocks);
+                in_code_block = false;
+            }
+            Event::Start(Tag::MetadataBlock(MetadataBlockKind::YamlStyle)) => in_metadata = true,
+            Event::End(TagEnd::MetadataBlock(_)) => in_metadata = false,
+            Event::Start(Tag::Image { .. }) => image_depth += 1,
+            Event::End(TagEnd::Image) => image_depth -= 1,
+            Event::Start(
+                Tag::Paragraph | Tag::Item | Tag::BlockQuote(_) | Tag::TableRow | Tag::TableHead,
+            )
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Item
+                | TagEnd::BlockQuote(_)
+                | TagEnd::TableRow
+                | TagEnd::TableHead,
+            )
+            | Event::HardBreak
+            | Event::Rule => flush(&mut text, &mut blocks),
+            Event::End(TagEnd::TableCell) => text.push(' '),
+            Event::Text(content) if in_metadata => front_matter.push_str(&content),
+            Event::Text(content) if in_code_block => {
+                // 代码块保留原有的分行
+                let mut segments = content.split('\n');
+
+                if let Some(first) = segments.next() {
+                    text.push_str(first);
+                }
+
+                for segment in segments {
+                    flush(&mut text, &mut blocks);
+                    text.push_str(segment);
+                }
+            }
+            Event::Text(content) | Event::Code(content) if image_depth == 0 => {
+                text.push_str(&content)
+            }
+            // 英文的软换行视为空格，中文则直接连接
+            Event::SoftBreak if text.chars().last().is_some_and(|c| c.is_ascii()) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    flush(&mut text, &mut blocks);
+
+    (blocks, front_matter)
+}
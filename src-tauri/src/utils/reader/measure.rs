@@ -0,0 +1,112 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use font_kit::{
+    family_name::FamilyName,
+    font::Font,
+    properties::{Properties, Weight},
+    source::SystemSource,
+};
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthChar;
+
+use super::wrap::wrap_by_width;
+
+// 按像素排版所需的窗口宽度与字体设置，与阅读器窗口的样式一致
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PixelFit {
+    // 阅读器窗口中文本区域的宽度（像素）
+    pub line_width: f64,
+    pub font_family: String,
+    pub font_size: f64,
+    pub font_weight: f64,
+    pub letter_spacing: f64,
+}
+
+thread_local! {
+    // 加载字体较慢，同一线程内复用上次的字体与字宽
+    static MEASURER: RefCell<Option<FontMeasurer>> = const { RefCell::new(None) };
+}
+
+// 按像素宽度将段落折成多行
+// 窗口边缘无法容纳悬挂标点，因此不允许超出，行首禁则字符连同前面的字一起移到下一行
+pub fn wrap_to_pixels(paragraph: &str, fit: &PixelFit) -> Vec<String> {
+    MEASURER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+
+        if !cell.as_ref().is_some_and(|measurer| measurer.fit == *fit) {
+            *cell = None;
+        }
+
+        let measurer = cell.get_or_insert_with(|| FontMeasurer::new(fit.clone()));
+
+        wrap_by_width(paragraph, fit.line_width, 0.0, &|char| {
+            measurer.char_width(char)
+        })
+    })
+}
+
+struct FontMeasurer {
+    fit: PixelFit,
+    // 找不到字体时为空，按全角、半角估算字宽
+    font: Option<Font>,
+    units_per_em: f64,
+    advances: RefCell<HashMap<char, f64>>,
+}
+
+impl FontMeasurer {
+    fn new(fit: PixelFit) -> Self {
+        let mut families = Vec::new();
+        if !fit.font_family.is_empty() {
+            families.push(FamilyName::Title(fit.font_family.clone()));
+        }
+        // 未设置字体时，阅读器窗口使用系统默认的无衬线字体
+        families.push(FamilyName::SansSerif);
+
+        let font = SystemSource::new()
+            .select_best_match(
+                &families,
+                Properties::new().weight(Weight(fit.font_weight as f32)),
+            )
+            .ok()
+            .and_then(|handle| handle.load().ok());
+
+        let units_per_em = font
+            .as_ref()
+            .map(|font| font.metrics().units_per_em as f64)
+            .unwrap_or(1000.0);
+
+        Self {
+            fit,
+            font,
+            units_per_em,
+            advances: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn char_width(&self, char: char) -> f64 {
+        if let Some(width) = self.advances.borrow().get(&char) {
+            return *width;
+        }
+
+        let columns = char.width().unwrap_or(0);
+
+        let advance = self
+            .font
+            .as_ref()
+            .and_then(|font| font.advance(font.glyph_for_char(char)?).ok())
+            .map(|advance| advance.x() as f64 * self.fit.font_size / self.units_per_em)
+            // 字体中没有该字符时，浏览器会改用其他字体显示，按全角为一个字号、半角为半个字号估算
+            .unwrap_or(columns as f64 * self.fit.font_size / 2.0);
+
+        // 字间距加在每个字符之后
+        let width = if columns > 0 {
+            advance + self.fit.letter_spacing
+        } else {
+            advance
+        };
+
+        self.advances.borrow_mut().insert(char, width);
+
+        width
+    }
+}
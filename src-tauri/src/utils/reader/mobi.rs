@@ -0,0 +1,638 @@
+use std::{collections::HashSet, fs};
+
+use encoding::{all::WINDOWS_1252, types::DecoderTrap, Encoding};
+use scraper::Html;
+
+use super::{
+    dom::{blocks_to_paragraphs, walk_blocks},
+    FileReader, NovelMetadata, Paragraph, ReaderOptions,
+};
+
+// 压缩方式
+const COMPRESSION_NONE: u16 = 1;
+const COMPRESSION_PALMDOC: u16 = 2;
+const COMPRESSION_HUFF_CDIC: u16 = 17480;
+
+// 文本编码
+const ENCODING_UTF8: u32 = 65001;
+
+// EXTH 记录类型
+const EXTH_AUTHOR: u32 = 100;
+const EXTH_DESCRIPTION: u32 = 103;
+const EXTH_COVER_OFFSET: u32 = 201;
+const EXTH_UPDATED_TITLE: u32 = 503;
+
+// KF8（AZW3）格式的 MOBI 头版本
+const KF8_VERSION: u32 = 8;
+
+// 表示“无”的索引值
+const NULL_INDEX: u32 = 0xFFFF_FFFF;
+
+pub struct MobiReader;
+
+impl FileReader for MobiReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let book = MobiBook::open(path)?;
+        let text = book.text()?;
+
+        let html = Html::parse_document(&text);
+        let blocks = walk_blocks(&html, &HashSet::new());
+
+        // 以一到三级标题作为章节
+        blocks_to_paragraphs(blocks, 3, &options.chapter_patterns)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        let book = MobiBook::open(path)?;
+
+        let title = book
+            .exth_string(EXTH_UPDATED_TITLE)
+            .or_else(|| book.full_name())
+            .filter(|title| !title.is_empty());
+
+        Ok(NovelMetadata {
+            title,
+            author: book.exth_string(EXTH_AUTHOR),
+            description: book.exth_string(EXTH_DESCRIPTION),
+            cover: book.cover(),
+            ..Default::default()
+        })
+    }
+}
+
+// PalmDB 容器中的 MOBI 电子书（含 PalmDOC、MOBI，以及同时包含 MOBI 格式正文的 AZW3）
+struct MobiBook {
+    data: Vec<u8>,
+    // 每条记录在文件中的起止位置
+    records: Vec<(usize, usize)>,
+    compression: u16,
+    text_length: usize,
+    text_record_count: usize,
+    text_encoding: u32,
+    // 每条文本记录末尾附加数据的标志位
+    extra_data_flags: u16,
+    huffman_record_offset: usize,
+    huffman_record_count: usize,
+    first_image_index: u32,
+    full_name: Option<(usize, usize)>,
+    exth: Vec<(u32, Vec<u8>)>,
+}
+
+impl MobiBook {
+    fn open(path: &str) -> Result<Self, String> {
+        Self::from_bytes(fs::read(path).map_err(|e| e.to_string())?)
+    }
+
+    fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        if data.len() < 78 {
+            return Err("无效的 MOBI 文件".to_string());
+        }
+
+        let book_type = &data[60..68];
+        if book_type != b"BOOKMOBI" && book_type != b"TEXtREAd" {
+            return Err("无效的 MOBI 文件".to_string());
+        }
+
+        let record_count = read_u16(&data, 76)? as usize;
+        let mut offsets = Vec::with_capacity(record_count);
+        for index in 0..record_count {
+            offsets.push(read_u32(&data, 78 + index * 8)? as usize);
+        }
+
+        let records: Vec<(usize, usize)> = offsets
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let end = offsets.get(index + 1).copied().unwrap_or(data.len());
+                (
+                    start.min(data.len()),
+                    end.clamp(start.min(data.len()), data.len()),
+                )
+            })
+            .collect();
+
+        let &(record0_start, record0_end) = records.first().ok_or("无效的 MOBI 文件")?;
+        let record0 = &data[record0_start..record0_end];
+
+        let compression = read_u16(record0, 0)?;
+        let text_length = read_u32(record0, 4)? as usize;
+        let text_record_count = read_u16(record0, 8)? as usize;
+        let encryption = read_u16(record0, 12)?;
+
+        if encryption != 0 {
+            return Err("不支持带 DRM 的电子书".to_string());
+        }
+
+        let mut text_encoding = 1252;
+        let mut extra_data_flags = 0;
+        let mut huffman_record_offset = 0;
+        let mut huffman_record_count = 0;
+        let mut first_image_index = NULL_INDEX;
+        let mut full_name = None;
+        let mut exth = Vec::new();
+
+        // PalmDOC 没有 MOBI 头
+        if record0.len() >= 0x84 && &record0[16..20] == b"MOBI" {
+            let header_length = read_u32(record0, 20)? as usize;
+
+            // 只有 KF8 格式的 AZW3 正文被拆分为骨架与片段，需要按索引重新拼接，暂不支持
+            // 同时包含 MOBI 格式的 AZW3 第一条记录仍是 MOBI 头，读取其中的 MOBI 正文
+            if read_u32(record0, 0x24)? >= KF8_VERSION {
+                return Err(
+                    "暂不支持只包含 KF8 格式的 AZW3 文件，请先转换为 EPUB 或 MOBI 后导入"
+                        .to_string(),
+                );
+            }
+
+            text_encoding = read_u32(record0, 28)?;
+            full_name = Some((
+                read_u32(record0, 0x54)? as usize,
+                read_u32(record0, 0x58)? as usize,
+            ));
+            first_image_index = read_u32(record0, 0x6C)?;
+            huffman_record_offset = read_u32(record0, 0x70)? as usize;
+            huffman_record_count = read_u32(record0, 0x74)? as usize;
+
+            if header_length >= 0xE4 {
+                extra_data_flags = read_u16(record0, 0xF2)?;
+            }
+
+            let exth_flags = read_u32(record0, 0x80)?;
+            if exth_flags & 0x40 != 0 {
+                exth = parse_exth(record0, 16 + header_length);
+            }
+        }
+
+        Ok(Self {
+            data,
+            records,
+            compression,
+            text_length,
+            text_record_count,
+            text_encoding,
+            extra_data_flags,
+            huffman_record_offset,
+            huffman_record_count,
+            first_image_index,
+            full_name,
+            exth,
+        })
+    }
+
+    fn record(&self, index: usize) -> Option<&[u8]> {
+        self.records
+            .get(index)
+            .map(|&(start, end)| &self.data[start..end])
+    }
+
+    fn text(&self) -> Result<String, String> {
+        let mut huff_reader = match self.compression {
+            COMPRESSION_HUFF_CDIC => {
+                let records = (self.huffman_record_offset
+                    ..self.huffman_record_offset + self.huffman_record_count)
+                    .map(|index| self.record(index).ok_or("MOBI 压缩字典缺失"))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Some(HuffCdicReader::new(&records)?)
+            }
+            COMPRESSION_NONE | COMPRESSION_PALMDOC => None,
+            _ => return Err("不支持的 MOBI 压缩格式".to_string()),
+        };
+
+        let mut text = Vec::with_capacity(self.text_length);
+
+        for index in 1..=self.text_record_count {
+            let record = self.record(index).ok_or("MOBI 文本记录缺失")?;
+            let trailing_size = trailing_entries_size(record, self.extra_data_flags);
+            let record = &record[..record.len().saturating_sub(trailing_size)];
+
+            match (&mut huff_reader, self.compression) {
+                (Some(huff_reader), _) => text.extend(huff_reader.unpack(record, 0)?),
+                (None, COMPRESSION_PALMDOC) => text.extend(palmdoc_decompress(record)),
+                _ => text.extend_from_slice(record),
+            }
+        }
+
+        text.truncate(self.text_length);
+
+        Ok(self.decode(&text))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> String {
+        if self.text_encoding == ENCODING_UTF8 {
+            String::from_utf8_lossy(bytes).to_string()
+        } else {
+            WINDOWS_1252
+                .decode(bytes, DecoderTrap::Replace)
+                .unwrap_or_default()
+        }
+    }
+
+    fn full_name(&self) -> Option<String> {
+        let (offset, length) = self.full_name?;
+        let record0 = self.record(0)?;
+        let bytes = record0.get(offset..offset + length)?;
+        Some(self.decode(bytes).trim().to_string())
+    }
+
+    fn exth_string(&self, record_type: u32) -> Option<String> {
+        self.exth
+            .iter()
+            .find(|(exth_type, _)| *exth_type == record_type)
+            .map(|(_, value)| self.decode(value).trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn cover(&self) -> Option<Vec<u8>> {
+        if self.first_image_index == NULL_INDEX {
+            return None;
+        }
+
+        let (_, offset) = self
+            .exth
+            .iter()
+            .find(|(exth_type, _)| *exth_type == EXTH_COVER_OFFSET)?;
+        let offset = read_u32(offset, 0).ok()?;
+
+        // 损坏的文件中偏移量可能溢出，视为没有封面
+        let index = self.first_image_index.checked_add(offset)?;
+        let cover = self.record(index as usize)?;
+
+        (!cover.is_empty()).then(|| cover.to_vec())
+    }
+}
+
+fn parse_exth(record0: &[u8], start: usize) -> Vec<(u32, Vec<u8>)> {
+    let mut exth = Vec::new();
+
+    if record0.get(start..start + 4) != Some(b"EXTH") {
+        return exth;
+    }
+
+    let Ok(record_count) = read_u32(record0, start + 8) else {
+        return exth;
+    };
+
+    let mut position = start + 12;
+    for _ in 0..record_count {
+        let (Ok(record_type), Ok(length)) =
+            (read_u32(record0, position), read_u32(record0, position + 4))
+        else {
+            break;
+        };
+
+        let length = length as usize;
+        let Some(value) = record0.get(position + 8..position + length.max(8)) else {
+            break;
+        };
+
+        exth.push((record_type, value.to_vec()));
+        position += length.max(8);
+    }
+
+    exth
+}
+
+// 计算文本记录末尾附加数据的长度
+fn trailing_entries_size(record: &[u8], flags: u16) -> usize {
+    let mut size = 0;
+    let mut test_flags = flags >> 1;
+
+    while test_flags != 0 {
+        if test_flags & 1 != 0 {
+            size += trailing_entry_size(&record[..record.len().saturating_sub(size)]);
+        }
+        test_flags >>= 1;
+    }
+
+    // 最低位表示存在多字节字符的续接数据
+    if flags & 1 != 0 {
+        if let Some(&byte) = record
+            .len()
+            .checked_sub(size + 1)
+            .and_then(|i| record.get(i))
+        {
+            size += (byte & 0x3) as usize + 1;
+        }
+    }
+
+    size
+}
+
+fn trailing_entry_size(data: &[u8]) -> usize {
+    let mut result = 0;
+    let mut bit_position = 0;
+
+    for &byte in data.iter().rev() {
+        result |= ((byte & 0x7F) as usize) << bit_position;
+        bit_position += 7;
+
+        if byte & 0x80 != 0 || bit_position >= 28 {
+            break;
+        }
+    }
+
+    result
+}
+
+// PalmDOC 的 LZ77 变体解压
+fn palmdoc_decompress(data: &[u8]) -> Vec<u8> {
+    let mut output: Vec<u8> = Vec::with_capacity(data.len() * 2);
+    let mut index = 0;
+
+    while index < data.len() {
+        let byte = data[index];
+        index += 1;
+
+        match byte {
+            0x01..=0x08 => {
+                let end = (index + byte as usize).min(data.len());
+                output.extend_from_slice(&data[index..end]);
+                index = end;
+            }
+            0x80..=0xBF => {
+                let Some(&next) = data.get(index) else {
+                    break;
+                };
+                index += 1;
+
+                let pair = (((byte as usize) << 8) | next as usize) & 0x3FFF;
+                let distance = pair >> 3;
+                let length = (pair & 0x7) + 3;
+
+                if distance == 0 || distance > output.len() {
+                    continue;
+                }
+
+                let start = output.len() - distance;
+                for offset in 0..length {
+                    output.push(output[start + offset]);
+                }
+            }
+            0xC0..=0xFF => {
+                output.push(b' ');
+                output.push(byte ^ 0x80);
+            }
+            _ => output.push(byte),
+        }
+    }
+
+    output
+}
+
+// HUFF/CDIC 压缩的解压器
+struct HuffCdicReader {
+    dict1: Vec<(u32, bool, u64)>,
+    min_codes: Vec<u64>,
+    max_codes: Vec<u64>,
+    // 词典中的每一项：内容及是否已完全解压
+    dictionary: Vec<(Vec<u8>, bool)>,
+}
+
+impl HuffCdicReader {
+    // 递归解压词典项时允许的最大深度
+    const MAX_DEPTH: usize = 32;
+
+    fn new(records: &[&[u8]]) -> Result<Self, String> {
+        let (huff, cdics) = records.split_first().ok_or("MOBI 压缩字典缺失")?;
+
+        if huff.get(0..8) != Some(b"HUFF\x00\x00\x00\x18") {
+            return Err("无效的 MOBI 压缩字典".to_string());
+        }
+
+        let dict1_offset = read_u32(huff, 8)? as usize;
+        let dict2_offset = read_u32(huff, 12)? as usize;
+
+        let mut dict1 = Vec::with_capacity(256);
+        for index in 0..256 {
+            let value = read_u32(huff, dict1_offset + index * 4)?;
+            let code_length = value & 0x1F;
+            let is_terminal = value & 0x80 != 0;
+            let max_code = (((value >> 8) as u64 + 1) << (32 - code_length)).wrapping_sub(1);
+            dict1.push((code_length, is_terminal, max_code));
+        }
+
+        let mut min_codes = vec![0];
+        let mut max_codes = vec![0];
+        for code_length in 1..=32u32 {
+            let offset = dict2_offset + (code_length as usize - 1) * 8;
+            let min_code = read_u32(huff, offset)? as u64;
+            let max_code = read_u32(huff, offset + 4)? as u64;
+            min_codes.push(min_code << (32 - code_length));
+            max_codes.push(((max_code + 1) << (32 - code_length)).wrapping_sub(1));
+        }
+
+        let mut dictionary: Vec<(Vec<u8>, bool)> = Vec::new();
+        for cdic in cdics {
+            if cdic.get(0..8) != Some(b"CDIC\x00\x00\x00\x10") {
+                return Err("无效的 MOBI 压缩字典".to_string());
+            }
+
+            let phrases = read_u32(cdic, 8)? as usize;
+            let bits = read_u32(cdic, 12)?;
+            let count = (1usize << bits.min(31)).min(phrases.saturating_sub(dictionary.len()));
+
+            for index in 0..count {
+                let offset = read_u16(cdic, 16 + index * 2)? as usize;
+                let length = read_u16(cdic, 16 + offset)?;
+                let start = 18 + offset;
+                let end = start + (length & 0x7FFF) as usize;
+                let slice = cdic.get(start..end).ok_or("无效的 MOBI 压缩字典")?;
+                dictionary.push((slice.to_vec(), length & 0x8000 != 0));
+            }
+        }
+
+        Ok(Self {
+            dict1,
+            min_codes,
+            max_codes,
+            dictionary,
+        })
+    }
+
+    fn unpack(&mut self, data: &[u8], depth: usize) -> Result<Vec<u8>, String> {
+        if depth > Self::MAX_DEPTH {
+            return Err("无效的 MOBI 压缩数据".to_string());
+        }
+
+        let mut padded = data.to_vec();
+        padded.extend_from_slice(&[0; 8]);
+
+        let read_u64 = |position: usize| {
+            u64::from_be_bytes(padded[position..position + 8].try_into().unwrap())
+        };
+
+        let mut bits_left = data.len() as i64 * 8;
+        let mut position = 0;
+        let mut x = read_u64(position);
+        let mut n: i64 = 32;
+        let mut output = Vec::new();
+
+        loop {
+            if n <= 0 {
+                position += 4;
+                if position + 8 > padded.len() {
+                    break;
+                }
+                x = read_u64(position);
+                n += 32;
+            }
+
+            let code = (x >> n) & 0xFFFF_FFFF;
+            let (mut code_length, is_terminal, mut max_code) = self.dict1[(code >> 24) as usize];
+
+            if !is_terminal {
+                while code_length < 32 && code < self.min_codes[code_length as usize] {
+                    code_length += 1;
+                }
+                max_code = self.max_codes[code_length as usize];
+            }
+
+            if code_length == 0 {
+                return Err("无效的 MOBI 压缩数据".to_string());
+            }
+
+            n -= code_length as i64;
+            bits_left -= code_length as i64;
+            if bits_left < 0 {
+                break;
+            }
+
+            let index = (max_code.wrapping_sub(code) >> (32 - code_length)) as usize;
+            let (slice, is_unpacked) = self
+                .dictionary
+                .get(index)
+                .cloned()
+                .ok_or("无效的 MOBI 压缩数据")?;
+
+            if is_unpacked {
+                output.extend_from_slice(&slice);
+            } else {
+                let slice = self.unpack(&slice, depth + 1)?;
+                output.extend_from_slice(&slice);
+                self.dictionary[index] = (slice, true);
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| "MOBI 文件已损坏".to_string())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| "MOBI 文件已损坏".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 只有一条记录的 BOOKMOBI 文件，第一条记录为指定版本的 MOBI 头
+    fn mobi_file(version: u32) -> Vec<u8> {
+        let mut data = vec![0; 60];
+        data.extend(b"BOOKMOBI");
+        data.extend([0; 8]);
+        data.extend(1u16.to_be_bytes());
+        data.extend(86u32.to_be_bytes());
+        data.extend([0; 4]);
+
+        let mut record0 = vec![0; 0xF8];
+        record0[0..2].copy_from_slice(&COMPRESSION_NONE.to_be_bytes());
+        record0[16..20].copy_from_slice(b"MOBI");
+        record0[20..24].copy_from_slice(&0xE8u32.to_be_bytes());
+        record0[28..32].copy_from_slice(&ENCODING_UTF8.to_be_bytes());
+        record0[0x24..0x28].copy_from_slice(&version.to_be_bytes());
+        data.extend(record0);
+
+        data
+    }
+
+    #[test]
+    fn rejects_kf8_only_books() {
+        assert!(MobiBook::from_bytes(mobi_file(6)).is_ok());
+
+        let error = MobiBook::from_bytes(mobi_file(8)).err().unwrap();
+        assert!(error.contains("KF8"), "{error}");
+    }
+
+    #[test]
+    fn palmdoc_literals_and_back_references() {
+        // "ab"，回溯距离 2、长度 3，空格加字符，2 个原样字节，距离超出输出的回溯被忽略
+        let data = [b'a', b'b', 0x80, 0x10, 0xE1, 0x02, 0xC8, 0x00, 0x80, 0xF8];
+
+        assert_eq!(palmdoc_decompress(&data), b"ababa a\xC8\x00");
+    }
+
+    #[test]
+    fn palmdoc_truncated_back_reference() {
+        assert_eq!(palmdoc_decompress(&[b'a', 0x80]), b"a");
+    }
+
+    // 两个 1 位编码：1 对应第一项，0 对应第二项
+    fn huff_cdic_records(second: (&[u8], bool)) -> (Vec<u8>, Vec<u8>) {
+        let mut huff = b"HUFF\x00\x00\x00\x18".to_vec();
+        huff.extend(24u32.to_be_bytes());
+        huff.extend((24u32 + 256 * 4).to_be_bytes());
+        huff.extend([0; 8]);
+        for _ in 0..256 {
+            huff.extend(0x181u32.to_be_bytes());
+        }
+        huff.extend([0; 32 * 8]);
+
+        let entries: [(&[u8], bool); 2] = [(b"ab", true), second];
+
+        let mut cdic = b"CDIC\x00\x00\x00\x10".to_vec();
+        cdic.extend(2u32.to_be_bytes());
+        cdic.extend(1u32.to_be_bytes());
+
+        let mut offset = entries.len() * 2;
+        let mut phrases = Vec::new();
+        for (phrase, is_unpacked) in entries {
+            cdic.extend((offset as u16).to_be_bytes());
+            let flag = if is_unpacked { 0x8000 } else { 0 };
+            phrases.extend((phrase.len() as u16 | flag).to_be_bytes());
+            phrases.extend(phrase);
+            offset += 2 + phrase.len();
+        }
+        cdic.extend(phrases);
+
+        (huff, cdic)
+    }
+
+    #[test]
+    fn huff_cdic_unpack() {
+        let (huff, cdic) = huff_cdic_records((b"cd", true));
+        let mut reader = HuffCdicReader::new(&[&huff, &cdic]).unwrap();
+
+        assert_eq!(
+            reader.unpack(&[0b1010_0101], 0).unwrap(),
+            b"abcdabcdcdabcdab"
+        );
+    }
+
+    #[test]
+    fn huff_cdic_unpacks_nested_phrases() {
+        // 第二项本身是压缩数据，展开为 8 个第一项
+        let (huff, cdic) = huff_cdic_records((&[0xFF], false));
+        let mut reader = HuffCdicReader::new(&[&huff, &cdic]).unwrap();
+
+        // 一个第二项和七个第一项
+        assert_eq!(reader.unpack(&[0b0111_1111], 0).unwrap(), b"ab".repeat(15));
+        // 展开后的结果被缓存，再次使用时结果相同
+        assert_eq!(reader.unpack(&[0x00], 0).unwrap(), b"ab".repeat(64));
+    }
+
+    #[test]
+    fn huff_cdic_rejects_invalid_header() {
+        let (_, cdic) = huff_cdic_records((b"cd", true));
+
+        assert!(HuffCdicReader::new(&[b"HUFF", &cdic]).is_err());
+        assert!(HuffCdicReader::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,719 @@
+pub mod archive;
+pub mod chapter;
+pub mod cleanup;
+pub mod convert;
+pub mod document;
+mod dom;
+mod epub;
+mod fb2;
+mod heuristic;
+mod html;
+mod markdown;
+pub mod measure;
+mod mobi;
+mod pdf;
+pub mod txt;
+mod wrap;
+
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use self::epub::EpubReader;
+use archive::ArchiveReader;
+use chapter::ChapterPreview;
+use cleanup::CleanupRule;
+use convert::ChineseConversion;
+use document::{Document, Layout};
+use fb2::Fb2Reader;
+use html::HtmlReader;
+use markdown::MarkdownReader;
+use measure::PixelFit;
+use mobi::MobiReader;
+use pdf::PdfReader;
+use txt::TxtReader;
+
+// 支持导入的文件扩展名
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "txt", "epub", "mobi", "azw3", "azw", "fb2", "fb2.zip", "md", "markdown", "html", "htm", "pdf",
+    "zip",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Chapter {
+    pub index: usize,
+    pub title: String,
+    pub start_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReaderOptions {
+    // 每行字数
+    pub line_size: usize,
+    // 按窗口宽度排版时的字体设置，为空时按每行字数排版
+    pub pixel_fit: Option<PixelFit>,
+    // 章节匹配正则
+    pub chapter_patterns: Vec<String>,
+    // 解析结果的缓存目录，为空时不缓存
+    pub cache_dir: Option<PathBuf>,
+    // 文本文件使用的编码，为空时自动检测
+    pub encoding: Option<String>,
+    // 简繁转换
+    pub conversion: ChineseConversion,
+    // 替换规则，按顺序执行
+    pub cleanup_rules: Vec<CleanupRule>,
+}
+
+impl ReaderOptions {
+    // 影响解析结果的设置，这些设置不变时可以复用已解析的文档，只重新排版
+    pub fn parse_key(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}",
+            self.encoding.as_deref().unwrap_or_default(),
+            self.conversion.as_str(),
+            serde_json::to_string(&self.cleanup_rules).unwrap_or_default(),
+            self.chapter_patterns.join("\n")
+        )
+    }
+
+    // 影响排版结果的设置，用于缓存各区块的行数
+    pub fn layout_key(&self) -> String {
+        format!(
+            "{}\n{}",
+            self.line_size,
+            serde_json::to_string(&self.pixel_fit).unwrap_or_default()
+        )
+    }
+}
+
+// 与排版无关的阅读位置：章节序号、章节内的段落序号、段落内的字符偏移
+// 每行字数、字体等变化后，可以据此找回对应的行
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct ReadAnchor {
+    pub chapter: usize,
+    pub paragraph: usize,
+    pub offset: usize,
+}
+
+// 从文件中读取的书籍信息
+#[derive(Debug, Default)]
+pub struct NovelMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub cover: Option<Vec<u8>>,
+    // 页数，仅 PDF 等分页的格式有
+    pub page_count: Option<i64>,
+}
+
+pub struct NovelReader {
+    pub novel_id: i64,
+    pub novel_path: String,
+    // 解析后的文档，排版时按区块读取正文
+    pub document: Arc<Document>,
+    pub chapters: Vec<Chapter>,
+    pub read_position: usize,
+    pub options: ReaderOptions,
+    // 各区块的起始行号，最后一项为总行数
+    section_starts: Vec<usize>,
+    // 已排版的区块：阅读位置所在的区块及其前后各一个，其余区块不占用内存
+    window: Vec<(usize, Vec<Line>)>,
+}
+
+// 阅读器的元数据，不包含正文
+#[derive(Debug, Serialize, Clone)]
+pub struct NovelReaderInfo {
+    pub novel_id: i64,
+    pub novel_path: String,
+    pub chapters: Vec<Chapter>,
+    pub read_position: usize,
+    pub total_lines: usize,
+    pub current_chapter: Chapter,
+    pub read_progress: f64,
+}
+
+impl NovelReader {
+    // 有阅读位置锚点时据此定位，否则使用保存的行号
+    pub fn new(
+        novel_id: i64,
+        novel_path: String,
+        read_position: usize,
+        read_anchor: Option<ReadAnchor>,
+        options: ReaderOptions,
+    ) -> Result<Self, String> {
+        let document = Document::load(&novel_path, &options)?;
+        let layout = document.layout(&options)?;
+
+        let mut reader = Self {
+            novel_id,
+            novel_path,
+            document: Arc::new(document),
+            chapters: layout.chapters,
+            read_position: 0,
+            options,
+            section_starts: layout.section_starts,
+            window: Vec::new(),
+        };
+
+        reader.read_position = match read_anchor {
+            Some(read_anchor) => reader.find_anchor(&read_anchor)?,
+            None => read_position.min(reader.total_lines()),
+        };
+
+        reader.load_window()?;
+
+        Ok(reader)
+    }
+
+    // 解析（或读取缓存）并排版全书
+    pub fn read_lines(
+        path: &str,
+        options: &ReaderOptions,
+    ) -> Result<(Vec<Line>, Vec<Chapter>), String> {
+        Document::load(path, options)?.lines(options)
+    }
+
+    pub fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        match file_format(path).as_str() {
+            "txt" => TxtReader::read_metadata(path),
+            "epub" => EpubReader::read_metadata(path),
+            "mobi" | "azw3" | "azw" => MobiReader::read_metadata(path),
+            "fb2" | "fb2.zip" => Fb2Reader::read_metadata(path),
+            "md" | "markdown" => MarkdownReader::read_metadata(path),
+            "html" | "htm" => HtmlReader::read_metadata(path),
+            "pdf" => PdfReader::read_metadata(path),
+            "zip" => ArchiveReader::read_metadata(path),
+            _ => Err("不支持的文件类型".to_string()),
+        }
+    }
+
+    // 导入前检查能否读出正文，目前只有 PDF 需要检查是否为没有文字层的扫描件
+    pub fn check_content(path: &str) -> Result<(), String> {
+        match file_format(path).as_str() {
+            "pdf" => PdfReader::check_text_layer(path),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_supported(path: &str) -> bool {
+        SUPPORTED_EXTENSIONS.contains(&file_format(path).as_str())
+    }
+
+    // 是否为可以手动指定编码的纯文本格式
+    pub fn is_plain_text(path: &str) -> bool {
+        matches!(
+            file_format(path).as_str(),
+            "txt" | "md" | "markdown" | "html" | "htm"
+        )
+    }
+
+    // 按给定参数解析文件，返回章节及其大小，不修改任何状态
+    pub fn preview_chapters(
+        path: &str,
+        options: &ReaderOptions,
+    ) -> Result<Vec<ChapterPreview>, String> {
+        let (lines, chapters) = Self::read_lines(path, options)?;
+
+        let previews = chapters
+            .iter()
+            .enumerate()
+            .map(|(index, chapter)| {
+                let end_line = chapters
+                    .get(index + 1)
+                    .map(|next_chapter| next_chapter.start_line)
+                    .unwrap_or(lines.len());
+
+                let chapter_lines = &lines[chapter.start_line..end_line];
+
+                ChapterPreview {
+                    index: chapter.index,
+                    title: chapter.title.clone(),
+                    start_line: chapter.start_line,
+                    line_count: chapter_lines.len(),
+                    char_count: chapter_lines
+                        .iter()
+                        .map(|line| line.content.chars().count())
+                        .sum(),
+                }
+            })
+            .collect();
+
+        Ok(previews)
+    }
+
+    pub fn current_chapter(&self) -> &Chapter {
+        let mut current_chapter = &self.chapters[0];
+
+        for chapter in self.chapters.iter() {
+            if chapter.start_line <= self.read_position {
+                current_chapter = chapter;
+            }
+        }
+
+        current_chapter
+    }
+
+    // 当前章节的所有段落，用于预览替换规则的效果
+    pub fn current_chapter_paragraphs(&self) -> Result<Vec<Paragraph>, String> {
+        let mut paragraphs = Vec::new();
+
+        for section in self.chapter_sections(self.current_chapter().index) {
+            paragraphs.append(&mut self.document.section_paragraphs(section)?);
+        }
+
+        Ok(paragraphs)
+    }
+
+    pub fn info(&self) -> NovelReaderInfo {
+        NovelReaderInfo {
+            novel_id: self.novel_id,
+            novel_path: self.novel_path.clone(),
+            chapters: self.chapters.clone(),
+            read_position: self.read_position,
+            total_lines: self.total_lines(),
+            current_chapter: self.current_chapter().clone(),
+            read_progress: self.read_progress(),
+        }
+    }
+
+    pub fn total_lines(&self) -> usize {
+        self.section_starts.last().copied().unwrap_or(0)
+    }
+
+    // 替换为重新解析、排版后的内容，并按阅读位置锚点找回原来读到的行
+    pub fn relayout(
+        &mut self,
+        document: Arc<Document>,
+        layout: Layout,
+        options: ReaderOptions,
+    ) -> Result<(), String> {
+        let read_anchor = self.read_anchor();
+
+        self.document = document;
+        self.chapters = layout.chapters;
+        self.section_starts = layout.section_starts;
+        self.options = options;
+        self.window.clear();
+        self.read_position = self.find_anchor(&read_anchor)?;
+
+        self.load_window()
+    }
+
+    // 当前阅读位置的锚点，已读完时段落序号为章节的段落数
+    pub fn read_anchor(&self) -> ReadAnchor {
+        let chapter = self.current_chapter().index;
+        let sections = self.chapter_sections(chapter);
+
+        if self.read_position >= self.total_lines() {
+            return ReadAnchor {
+                chapter,
+                paragraph: sections.map(|section| self.paragraph_units(section)).sum(),
+                offset: 0,
+            };
+        }
+
+        let section = self.section_at(self.read_position);
+
+        // 章节中位于当前区块之前的段落数
+        let paragraph: usize = (sections.start..section)
+            .map(|section| self.paragraph_units(section))
+            .sum();
+
+        let index = self.read_position - self.section_starts[section];
+
+        // 缓存的行数与实际排版不一致时（如系统字体变化），按区块开头处理
+        match self
+            .section_lines(section)
+            .filter(|lines| index < lines.len())
+        {
+            Some(lines) => ReadAnchor {
+                chapter,
+                paragraph: paragraph
+                    + lines[..=index]
+                        .iter()
+                        .filter(|line| line.offset == 0)
+                        .count()
+                        .saturating_sub(1),
+                offset: lines[index].offset,
+            },
+            None => ReadAnchor {
+                chapter,
+                paragraph,
+                offset: 0,
+            },
+        }
+    }
+
+    // 锚点对应的行：所在段落中起始偏移不超过锚点偏移的最后一行
+    // 章节或段落超出范围时（如章节规则改变），定位到章节或全书的末尾
+    fn find_anchor(&self, read_anchor: &ReadAnchor) -> Result<usize, String> {
+        let chapter = read_anchor.chapter.min(self.chapters.len() - 1);
+        let sections = self.chapter_sections(chapter);
+
+        let mut paragraph = read_anchor.paragraph;
+
+        for section in sections.clone() {
+            let units = self.paragraph_units(section);
+
+            if paragraph >= units {
+                paragraph -= units;
+                continue;
+            }
+
+            // 区块中的段落都是完整的，区块的第一行总是段落的开始
+            let lines = self.document.layout_section(section, &self.options)?;
+
+            let mut current = 0;
+            let mut position = None;
+
+            for (index, line) in lines.iter().enumerate() {
+                if line.offset == 0 && index > 0 {
+                    current += 1;
+                }
+
+                if current > paragraph {
+                    break;
+                }
+
+                if current == paragraph && line.offset <= read_anchor.offset {
+                    position = Some(index);
+                }
+            }
+
+            return Ok(self.section_starts[section] + position.unwrap_or(lines.len()));
+        }
+
+        Ok(self.section_starts[sections.end])
+    }
+
+    // 章节包含的区块，第一个章节之前的内容算作第一个章节
+    fn chapter_sections(&self, chapter: usize) -> Range<usize> {
+        let start = match chapter {
+            0 => 0,
+            _ => self.section_at(self.chapters[chapter].start_line),
+        };
+
+        let end = self
+            .chapters
+            .get(chapter + 1)
+            .map(|chapter| self.section_at(chapter.start_line))
+            .unwrap_or(self.document.sections.len());
+
+        start..end
+    }
+
+    // 行所在的区块，每个区块至少有一行
+    fn section_at(&self, line: usize) -> usize {
+        self.section_starts
+            .partition_point(|start| *start <= line)
+            .saturating_sub(1)
+            .min(self.document.sections.len() - 1)
+    }
+
+    // 区块中的段落数，章节标题也算作一个段落
+    fn paragraph_units(&self, section: usize) -> usize {
+        let section = &self.document.sections[section];
+
+        section.paragraph_count + usize::from(section.title.is_some())
+    }
+
+    fn section_lines(&self, section: usize) -> Option<&[Line]> {
+        self.window
+            .iter()
+            .find(|(index, _)| *index == section)
+            .map(|(_, lines)| lines.as_slice())
+    }
+
+    // 阅读位置变化后，排版附近的区块，并释放较远的区块
+    fn load_window(&mut self) -> Result<(), String> {
+        let section = self.section_at(self.read_position);
+        let range = section.saturating_sub(1)..=(section + 1).min(self.document.sections.len() - 1);
+
+        self.window.retain(|(index, _)| range.contains(index));
+
+        for index in range {
+            if self.section_lines(index).is_none() {
+                let lines = self.document.layout_section(index, &self.options)?;
+                self.window.push((index, lines));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read_progress(&self) -> f64 {
+        self.read_position as f64 / self.total_lines() as f64 * 100.0
+    }
+
+    pub fn get_line(&self) -> Option<&Line> {
+        self.line_at(self.read_position)
+    }
+
+    // 从当前行到所在段落结束的各行
+    pub fn paragraph_lines(&self) -> Vec<&Line> {
+        let mut lines = Vec::new();
+        let mut position = self.read_position;
+
+        while let Some(line) = self.line_at(position) {
+            lines.push(line);
+
+            if line.is_paragraph_end {
+                break;
+            }
+
+            position += 1;
+        }
+
+        lines
+    }
+
+    // 只能读取已排版的区块中的行，段落不会跨越区块
+    fn line_at(&self, position: usize) -> Option<&Line> {
+        if position >= self.total_lines() {
+            return None;
+        }
+
+        let section = self.section_at(position);
+        let lines = self.section_lines(section)?;
+
+        lines.get(position - self.section_starts[section])
+    }
+
+    pub fn set_read_position(&mut self, read_position: usize) -> Result<(), String> {
+        if read_position >= self.total_lines() {
+            return Err("行号超出范围".to_string());
+        }
+
+        self.read_position = read_position;
+
+        self.load_window()
+    }
+
+    pub fn next_line(&mut self) -> Result<(), String> {
+        if self.read_position >= self.total_lines() {
+            return Err("行号超出范围".to_string());
+        }
+
+        self.read_position += 1;
+
+        self.load_window()
+    }
+
+    pub fn prev_line(&mut self) -> Result<(), String> {
+        if self.read_position == 0 {
+            return Err("行号超出范围".to_string());
+        }
+
+        self.read_position -= 1;
+
+        self.load_window()
+    }
+
+    // 跳到下一段落的第一行
+    pub fn next_paragraph(&mut self) -> Result<(), String> {
+        if self.read_position >= self.total_lines() {
+            return Err("行号超出范围".to_string());
+        }
+
+        self.read_position += self.paragraph_lines().len().max(1);
+
+        self.load_window()
+    }
+
+    // 跳到上一段落的第一行，位于段落中间时跳到本段落的第一行
+    pub fn prev_paragraph(&mut self) -> Result<(), String> {
+        if self.read_position == 0 {
+            return Err("行号超出范围".to_string());
+        }
+
+        let mut position = self.read_position - 1;
+
+        while position > 0 {
+            match self.line_at(position) {
+                Some(line) if !line.is_paragraph_start => position -= 1,
+                _ => break,
+            }
+        }
+
+        self.read_position = position;
+
+        self.load_window()
+    }
+
+    pub fn next_chapter(&mut self) -> Result<(), String> {
+        let current_chapter = self.current_chapter();
+
+        let next_chapter_index = self
+            .chapters
+            .iter()
+            .position(|chapter| chapter.start_line == current_chapter.start_line);
+
+        if let Some(next_chapter_index) = next_chapter_index {
+            if next_chapter_index + 1 >= self.chapters.len() {
+                return Err("没有下一章节".to_string());
+            }
+
+            let next_chapter = &self.chapters[next_chapter_index + 1];
+            self.read_position = next_chapter.start_line;
+        }
+
+        self.load_window()
+    }
+
+    pub fn prev_chapter(&mut self) -> Result<(), String> {
+        let current_chapter = self.current_chapter();
+
+        let prev_chapter_index = self
+            .chapters
+            .iter()
+            .position(|chapter| chapter.start_line == current_chapter.start_line);
+
+        if let Some(prev_chapter_index) = prev_chapter_index {
+            if prev_chapter_index == 0 {
+                return Err("没有上一章节".to_string());
+            }
+
+            let prev_chapter = &self.chapters[prev_chapter_index - 1];
+            self.read_position = prev_chapter.start_line;
+        }
+
+        self.load_window()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Line {
+    pub is_chapter: bool,
+    pub content: String,
+    // 该行在所属段落中的字符偏移，为 0 时表示段落的开始，章节标题单独作为一个段落
+    pub offset: usize,
+    // 是否为段落的第一行，否则为上一行的续行
+    pub is_paragraph_start: bool,
+    // 是否为段落的最后一行
+    pub is_paragraph_end: bool,
+}
+
+// 从文件中解析出的段落，章节标题单独作为一个段落
+#[derive(Debug, Clone)]
+pub struct Paragraph {
+    pub is_chapter: bool,
+    pub text: String,
+}
+
+trait FileReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String>;
+
+    // 读取书名、作者等信息，格式本身不包含这些信息时返回空值
+    fn read_metadata(_path: &str) -> Result<NovelMetadata, String> {
+        Ok(NovelMetadata::default())
+    }
+}
+
+// 文件格式，即小写的扩展名；.fb2.zip 这类压缩后的格式保留两级扩展名
+fn file_format(path: &str) -> String {
+    let path = Path::new(path);
+
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let inner_extension = path
+        .file_stem()
+        .map(Path::new)
+        .and_then(Path::extension)
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    match (extension.as_str(), inner_extension) {
+        ("zip", Some(inner_extension)) if inner_extension == "fb2" => "fb2.zip".to_string(),
+        _ => extension,
+    }
+}
+
+fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+    match file_format(path).as_str() {
+        "txt" => TxtReader::read_paragraphs(path, options),
+        "epub" => EpubReader::read_paragraphs(path, options),
+        "mobi" | "azw3" | "azw" => MobiReader::read_paragraphs(path, options),
+        "fb2" | "fb2.zip" => Fb2Reader::read_paragraphs(path, options),
+        "md" | "markdown" => MarkdownReader::read_paragraphs(path, options),
+        "html" | "htm" => HtmlReader::read_paragraphs(path, options),
+        "pdf" => PdfReader::read_paragraphs(path, options),
+        "zip" => ArchiveReader::read_paragraphs(path, options),
+        _ => Err("不支持的文件类型".to_string()),
+    }
+}
+
+// 追加一个正文段落，去掉首尾空白，忽略空段落
+fn push_paragraph(paragraphs: &mut Vec<Paragraph>, text: &str) {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return;
+    }
+
+    paragraphs.push(Paragraph {
+        is_chapter: false,
+        text: text.to_string(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn paragraph_lines_stop_at_section_boundary() {
+        let path = std::env::temp_dir().join("reader-paragraph-lines-test.txt");
+        let paragraph = "风从北方吹来，".repeat(6);
+        fs::write(
+            &path,
+            format!("第一章 风起\n{paragraph}\n第二章 云涌\n云层翻滚。\n"),
+        )
+        .unwrap();
+
+        let path = path.to_string_lossy().to_string();
+        let options = ReaderOptions {
+            line_size: 10,
+            chapter_patterns: chapter::resolve_chapter_patterns(None, &[]),
+            ..Default::default()
+        };
+
+        let (lines, _) = NovelReader::read_lines(&path, &options).unwrap();
+        let next_title = lines
+            .iter()
+            .position(|line| line.is_chapter && line.content == "第二章 云涌")
+            .unwrap();
+
+        let mut reader = NovelReader::new(1, path.clone(), 1, None, options).unwrap();
+        let _ = fs::remove_file(&path);
+
+        // 第一章最后一个段落跨多行，按段落读取时停在区块结尾，不包含下一章标题
+        let paragraph_lines = reader.paragraph_lines();
+        assert_eq!(paragraph_lines.len(), next_title - 1);
+        assert!(paragraph_lines.len() > 1);
+        assert!(paragraph_lines.last().unwrap().is_paragraph_end);
+        assert!(paragraph_lines.iter().all(|line| !line.is_chapter));
+
+        // 段落中间的行只返回到段落结尾
+        reader.set_read_position(next_title - 1).unwrap();
+        assert_eq!(reader.paragraph_lines().len(), 1);
+
+        // 下一区块从章节标题开始
+        reader.next_line().unwrap();
+        assert_eq!(reader.get_line().unwrap().content, "第二章 云涌");
+        assert_eq!(reader.paragraph_lines().len(), 1);
+
+        // 已读完时没有内容
+        reader.set_read_position(reader.total_lines() - 1).unwrap();
+        reader.next_line().unwrap();
+        assert!(reader.get_line().is_none());
+        assert!(reader.paragraph_lines().is_empty());
+    }
+}
@@ -0,0 +1,510 @@
+use std::{
+    collections::HashMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+use pdf_extract::{output_doc, Document, MediaBox, OutputDev, OutputError, Transform};
+use regex::Regex;
+
+use super::{
+    dom::{blocks_to_paragraphs, is_same_title, Block},
+    FileReader, NovelMetadata, Paragraph, ReaderOptions,
+};
+
+// 平均每页少于该字数时，认为 PDF 没有文字层
+const MIN_CHARS_PER_PAGE: usize = 20;
+
+pub struct PdfReader;
+
+impl FileReader for PdfReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let doc = load_document(path)?;
+        let pages = extract_pages(&doc)?;
+        let paragraphs = build_paragraphs(&pages);
+
+        // 段落按起始页分组
+        let mut page_paragraphs: Vec<Vec<&str>> = vec![Vec::new(); pages.len()];
+        for (page_index, text) in &paragraphs {
+            page_paragraphs[*page_index].push(text);
+        }
+
+        // 书签按页分组，页码从 1 开始
+        let mut outline: HashMap<usize, Vec<String>> = HashMap::new();
+        if let Ok(toc) = doc.get_toc() {
+            for entry in toc.toc {
+                let title = entry.title.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !title.is_empty() && entry.page > 0 {
+                    outline.entry(entry.page - 1).or_default().push(title);
+                }
+            }
+        }
+
+        let mut blocks: Vec<Block> = Vec::new();
+
+        for (page_index, page_paragraphs) in page_paragraphs.into_iter().enumerate() {
+            let mut titles = outline.remove(&page_index).unwrap_or_default();
+
+            // 页内找不到对应文字的书签放在页首，能找到的放在该段落处并替换该段落
+            let (matched, unmatched): (Vec<String>, Vec<String>) =
+                titles.drain(..).partition(|title| {
+                    page_paragraphs
+                        .iter()
+                        .any(|paragraph| is_same_title(title, paragraph))
+                });
+
+            for title in unmatched {
+                blocks.push(Block::Heading {
+                    level: 1,
+                    text: title,
+                });
+            }
+
+            let mut matched = matched.into_iter().peekable();
+
+            for paragraph in page_paragraphs {
+                if matched
+                    .peek()
+                    .is_some_and(|title| is_same_title(title, paragraph))
+                {
+                    blocks.push(Block::Heading {
+                        level: 1,
+                        text: matched.next().unwrap_or_default(),
+                    });
+                } else {
+                    blocks.push(Block::Paragraph(paragraph.to_string()));
+                }
+            }
+
+            for title in matched {
+                blocks.push(Block::Heading {
+                    level: 1,
+                    text: title,
+                });
+            }
+        }
+
+        // 有书签时以书签作为章节，否则按章节规则识别
+        blocks_to_paragraphs(blocks, 1, &options.chapter_patterns)
+    }
+
+    fn read_metadata(path: &str) -> Result<NovelMetadata, String> {
+        let doc = load_document(path)?;
+
+        let info = doc
+            .trailer
+            .get(b"Info")
+            .and_then(|info| doc.dereference(info))
+            .and_then(|(_, info)| info.as_dict())
+            .ok();
+
+        let field = |key: &[u8]| {
+            info.and_then(|info| info.get(key).ok())
+                .and_then(|value| doc.dereference(value).ok())
+                .and_then(|(_, value)| value.as_str().ok())
+                .map(decode_text_string)
+                .filter(|value| !value.is_empty())
+        };
+
+        Ok(NovelMetadata {
+            title: field(b"Title"),
+            author: field(b"Author"),
+            description: field(b"Subject"),
+            page_count: Some(doc.get_pages().len() as i64),
+            ..Default::default()
+        })
+    }
+}
+
+impl PdfReader {
+    // 导入前检查 PDF 是否有文字层，扫描件等没有文字的 PDF 无法阅读
+    pub fn check_text_layer(path: &str) -> Result<(), String> {
+        extract_pages(&load_document(path)?).map(|_| ())
+    }
+}
+
+fn load_document(path: &str) -> Result<Document, String> {
+    let buffer = fs::read(path).map_err(|e| e.to_string())?;
+    let mut doc = Document::load_mem(&buffer).map_err(|e| format!("PDF 解析失败: {e}"))?;
+
+    // 只有权限密码的 PDF 可以用空密码解密
+    if doc.is_encrypted() && doc.decrypt("").is_err() {
+        return Err("不支持加密的 PDF".to_string());
+    }
+
+    Ok(doc)
+}
+
+// 提取各页的文字行，文字过少时认为是扫描件
+fn extract_pages(doc: &Document) -> Result<Vec<Vec<TextLine>>, String> {
+    let mut collector = GlyphCollector::default();
+
+    // pdf-extract 遇到不支持的字体或损坏的内容流时会 panic，不能因此导致程序崩溃
+    match panic::catch_unwind(AssertUnwindSafe(|| output_doc(doc, &mut collector))) {
+        Ok(result) => result.map_err(|e| format!("PDF 解析失败: {e}"))?,
+        Err(_) => return Err("PDF 解析失败: 文件使用了不支持的字体或内容已损坏".to_string()),
+    }
+
+    let pages: Vec<Vec<TextLine>> = collector.pages.into_iter().map(build_lines).collect();
+
+    let char_count: usize = pages
+        .iter()
+        .flatten()
+        .map(|line| line.text.chars().filter(|c| !c.is_whitespace()).count())
+        .sum();
+
+    if char_count < pages.len().max(1) * MIN_CHARS_PER_PAGE {
+        return Err("PDF 中没有可提取的文字，可能是扫描件，暂不支持导入".to_string());
+    }
+
+    Ok(pages)
+}
+
+// 页面上的一个字符
+struct Glyph {
+    x: f64,
+    y: f64,
+    advance: f64,
+    size: f64,
+    text: String,
+}
+
+// 页面上的一行文字
+struct TextLine {
+    text: String,
+    left: f64,
+    right: f64,
+    top: f64,
+    size: f64,
+}
+
+#[derive(Default)]
+struct GlyphCollector {
+    pages: Vec<Vec<Glyph>>,
+    current: Vec<Glyph>,
+}
+
+impl OutputDev for GlyphCollector {
+    fn begin_page(
+        &mut self,
+        _page_num: u32,
+        _media_box: &MediaBox,
+        _art_box: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), OutputError> {
+        self.current.clear();
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        self.pages.push(std::mem::take(&mut self.current));
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &Transform,
+        width: f64,
+        spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> Result<(), OutputError> {
+        if char.trim().is_empty() {
+            return Ok(());
+        }
+
+        let scale = (trm.m11 * trm.m22 - trm.m12 * trm.m21).abs().sqrt();
+
+        self.current.push(Glyph {
+            x: trm.m31,
+            y: trm.m32,
+            advance: (width * font_size + spacing) * trm.m11,
+            size: font_size * scale,
+            text: char.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+}
+
+// 按坐标将字符排成行：从上到下，从左到右
+fn build_lines(mut glyphs: Vec<Glyph>) -> Vec<TextLine> {
+    glyphs.sort_by(|a, b| b.y.total_cmp(&a.y));
+
+    let mut rows: Vec<Vec<Glyph>> = Vec::new();
+    for glyph in glyphs {
+        match rows.last_mut() {
+            Some(row) if (row[0].y - glyph.y).abs() <= row[0].size.max(glyph.size) * 0.5 => {
+                row.push(glyph)
+            }
+            _ => rows.push(vec![glyph]),
+        }
+    }
+
+    // 页码等页眉页脚
+    let page_number =
+        Regex::new(r"(?i)^(第\s*\d+\s*页|[-–—]?\s*\d+\s*[-–—]?|page\s*\d+(\s*of\s*\d+)?)$")
+            .unwrap();
+
+    let mut lines = Vec::new();
+
+    for mut row in rows {
+        row.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let mut text = String::new();
+        let mut end: Option<f64> = None;
+        let mut last_char = ' ';
+
+        for glyph in &row {
+            let first_char = glyph.text.chars().next().unwrap_or(' ');
+
+            if let Some(end) = end {
+                // 中文字符间距较大，只有明显的空隙才视为空格
+                let threshold = if first_char.is_ascii() && last_char.is_ascii() {
+                    0.15
+                } else {
+                    0.6
+                };
+
+                if glyph.x - end > glyph.size * threshold {
+                    text.push(' ');
+                }
+            }
+
+            text.push_str(&glyph.text);
+            end = Some(glyph.x + glyph.advance);
+            last_char = glyph.text.chars().last().unwrap_or(' ');
+        }
+
+        let text = text.trim().to_string();
+
+        if text.is_empty() || page_number.is_match(&text) {
+            continue;
+        }
+
+        lines.push(TextLine {
+            text,
+            left: row[0].x,
+            right: end.unwrap_or(row[0].x),
+            top: row[0].y,
+            size: row.iter().map(|glyph| glyph.size).fold(0.0, f64::max),
+        });
+    }
+
+    lines
+}
+
+// 将硬换行的行合并为段落，返回段落及其起始页
+fn build_paragraphs(pages: &[Vec<TextLine>]) -> Vec<(usize, String)> {
+    let mut paragraphs: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    // 上一行是否写满一行，写满时下一行是同一段落的延续
+    let mut continues = false;
+
+    for (page_index, lines) in pages.iter().enumerate() {
+        if lines.is_empty() {
+            continue;
+        }
+
+        let left = lines.iter().map(|line| line.left).fold(f64::MAX, f64::min);
+        let right = percentile(lines.iter().map(|line| line.right).collect(), 0.9);
+        let line_gap = percentile(
+            lines
+                .windows(2)
+                .map(|pair| pair[0].top - pair[1].top)
+                .filter(|gap| *gap > 0.0)
+                .collect(),
+            0.5,
+        );
+
+        for (index, line) in lines.iter().enumerate() {
+            let indented = line.left - left > line.size;
+            let separated =
+                index > 0 && line_gap > 0.0 && lines[index - 1].top - line.top > line_gap * 1.5;
+
+            match current.as_mut() {
+                Some((_, paragraph)) if continues && !indented && !separated => {
+                    join_line(paragraph, &line.text)
+                }
+                _ => {
+                    paragraphs.extend(current.take());
+                    current = Some((page_index, line.text.clone()));
+                }
+            }
+
+            // 写满一行、以连字符断词，或未以句末标点结尾且接近行尾（左对齐排版），都视为段落未结束
+            let gap = right - line.right;
+            continues = gap < line.size * 2.0
+                || is_hyphenated(&line.text)
+                || (!ends_sentence(&line.text) && gap < (right - left) * 0.25);
+        }
+    }
+
+    paragraphs.extend(current);
+
+    paragraphs
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', '”', '’', '」', '』', ')', '）'])
+        .ends_with(['.', '!', '?', ':', '。', '！', '？', '…', '：'])
+}
+
+// 英文单词在行尾以连字符断开
+fn is_hyphenated(text: &str) -> bool {
+    let mut chars = text.chars().rev();
+    chars.next() == Some('-') && chars.next().is_some_and(char::is_alphabetic)
+}
+
+fn join_line(paragraph: &mut String, line: &str) {
+    let last = paragraph.chars().last().unwrap_or(' ');
+    let first = line.chars().next().unwrap_or(' ');
+
+    // 断开的单词去掉连字符直接连接
+    if is_hyphenated(paragraph) && first.is_lowercase() {
+        paragraph.pop();
+    } else if last.is_ascii() && first.is_ascii() {
+        paragraph.push(' ');
+    }
+
+    paragraph.push_str(line);
+}
+
+fn percentile(mut values: Vec<f64>, ratio: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    values.sort_by(f64::total_cmp);
+    let index = ((values.len() - 1) as f64 * ratio).round() as usize;
+
+    values[index]
+}
+
+// PDF 文本字符串：带 BOM 的 UTF-16BE，否则按 PDFDocEncoding（近似为 Latin-1）解码
+fn decode_text_string(bytes: &[u8]) -> String {
+    let text = match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).to_string(),
+        _ => bytes.iter().map(|&byte| byte as char).collect(),
+    };
+
+    text.trim_matches(|c: char| c.is_whitespace() || c == '\0')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_wrapped_lines_into_paragraphs() {
+        let page = vec![
+            TextLine {
+                text: "The quick brown fox jumps over the lazy".to_string(),
+                left: 72.0,
+                right: 520.0,
+                top: 700.0,
+                size: 12.0,
+            },
+            TextLine {
+                text: "dog and keeps run-".to_string(),
+                left: 72.0,
+                right: 515.0,
+                top: 686.0,
+                size: 12.0,
+            },
+            TextLine {
+                text: "ning far away.".to_string(),
+                left: 72.0,
+                right: 160.0,
+                top: 672.0,
+                size: 12.0,
+            },
+            TextLine {
+                text: "Next paragraph starts here.".to_string(),
+                left: 96.0,
+                right: 300.0,
+                top: 658.0,
+                size: 12.0,
+            },
+        ];
+
+        assert_eq!(
+            build_paragraphs(&[page]),
+            [
+                (
+                    0,
+                    "The quick brown fox jumps over the lazy dog and keeps running far away."
+                        .to_string()
+                ),
+                (0, "Next paragraph starts here.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn continues_paragraphs_across_pages() {
+        let pages = vec![
+            vec![TextLine {
+                text: "第一页写满的一行文字".to_string(),
+                left: 72.0,
+                right: 520.0,
+                top: 700.0,
+                size: 12.0,
+            }],
+            vec![
+                TextLine {
+                    text: "延续到第二页。".to_string(),
+                    left: 72.0,
+                    right: 250.0,
+                    top: 700.0,
+                    size: 12.0,
+                },
+                TextLine {
+                    text: "新的一段。".to_string(),
+                    left: 96.0,
+                    right: 200.0,
+                    top: 686.0,
+                    size: 12.0,
+                },
+            ],
+        ];
+
+        assert_eq!(
+            build_paragraphs(&pages),
+            [
+                (0, "第一页写满的一行文字延续到第二页。".to_string()),
+                (1, "新的一段。".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn separates_ascii_words_only() {
+        let mut paragraph = "word".to_string();
+        join_line(&mut paragraph, "next");
+        assert_eq!(paragraph, "word next");
+
+        let mut paragraph = "第一行".to_string();
+        join_line(&mut paragraph, "第二行");
+        assert_eq!(paragraph, "第一行第二行");
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+};
+
+use charset_normalizer_rs::{entity::NormalizerSettings, from_bytes, utils::decode};
+use encoding::{label::encoding_from_whatwg_label, types::DecoderTrap};
+use serde::{Deserialize, Serialize};
+
+use super::{chapter::detect_chapter_lines, push_paragraph, FileReader, Paragraph, ReaderOptions};
+
+pub struct TxtReader;
+
+impl FileReader for TxtReader {
+    fn read_paragraphs(path: &str, options: &ReaderOptions) -> Result<Vec<Paragraph>, String> {
+        let decoded_string = read_text(path, options.encoding.as_deref())?;
+
+        let raw_lines: Vec<&str> = decoded_string.lines().map(str::trim).collect();
+
+        let is_chapter_line = detect_chapter_lines(&raw_lines, &options.chapter_patterns)?;
+
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+
+        for (index, line) in raw_lines.into_iter().enumerate() {
+            // 是章节，直接添加行
+            if is_chapter_line[index] {
+                paragraphs.push(Paragraph {
+                    is_chapter: true,
+                    text: line.to_string(),
+                });
+            } else {
+                push_paragraph(&mut paragraphs, line);
+            }
+        }
+
+        Ok(paragraphs)
+    }
+}
+
+// 手动选择编码时列出的常用编码，即使自动检测没有选中也会列出
+const COMMON_ENCODINGS: &[&str] = &[
+    "utf-8",
+    "gb18030",
+    "gbk",
+    "big5",
+    "utf-16le",
+    "utf-16be",
+    "shift_jis",
+    "euc-kr",
+];
+
+// 候选编码的示例文本长度（字节）
+const SAMPLE_BYTES: usize = 600;
+
+// 文件可能使用的编码，及按该编码解码的示例文本
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncodingCandidate {
+    pub encoding: String,
+    // 可信度（0 ~ 1），由乱码比例换算，自动检测排除的编码为 0
+    pub confidence: f64,
+    pub sample: String,
+}
+
+// 读取文本文件，未指定编码时自动检测
+pub fn read_text(path: &str, encoding: Option<&str>) -> Result<String, String> {
+    decode_text(read_bytes(path)?, encoding)
+}
+
+fn read_bytes(path: &str) -> Result<Vec<u8>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut buf_reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+
+    buf_reader
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+
+    Ok(buffer)
+}
+
+// 按指定编码解码，未指定编码时自动检测
+pub fn decode_text(buffer: Vec<u8>, encoding: Option<&str>) -> Result<String, String> {
+    if let Some(encoding) = encoding {
+        let text = decode(&buffer, encoding, DecoderTrap::Replace, false, false)?;
+
+        return Ok(text.trim_start_matches('\u{feff}').to_string());
+    }
+
+    // 带 BOM 的 Unicode 文本直接解码
+    match buffer.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => return Ok(String::from_utf8_lossy(rest).to_string()),
+        [0xFF, 0xFE, rest @ ..] => return Ok(decode_utf16(rest, u16::from_le_bytes)),
+        [0xFE, 0xFF, rest @ ..] => return Ok(decode_utf16(rest, u16::from_be_bytes)),
+        _ => {}
+    }
+
+    let buffer = match String::from_utf8(buffer) {
+        Ok(text) => return Ok(text),
+        Err(e) => e.into_bytes(),
+    };
+
+    // 检测结果按连贯度排序，短文本时单字节编码可能排在前面，优先选择乱码最少的编码
+    let result = from_bytes(&buffer, None);
+    let encoding = result
+        .iter()
+        .reduce(|best, item| {
+            if item.chaos() < best.chaos() {
+                item
+            } else {
+                best
+            }
+        })
+        .ok_or("无法检测文件编码")?
+        .encoding();
+
+    decode(&buffer, encoding, DecoderTrap::Replace, false, false)
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+
+    String::from_utf16_lossy(&units)
+}
+
+// 检测文件可能使用的编码，按可信度从高到低排列
+pub fn encoding_candidates(path: &str) -> Result<Vec<EncodingCandidate>, String> {
+    let buffer = read_bytes(path)?;
+
+    let mut candidates: Vec<EncodingCandidate> = from_bytes(&buffer, None)
+        .iter()
+        .map(|item| EncodingCandidate {
+            encoding: item.encoding().to_string(),
+            confidence: 1.0 - item.chaos() as f64,
+            sample: sample_text(&buffer, item.encoding()),
+        })
+        .collect();
+
+    for encoding in COMMON_ENCODINGS {
+        let canonical = encoding_from_whatwg_label(encoding)
+            .and_then(|encoding| encoding.whatwg_name())
+            .unwrap_or(encoding);
+
+        if candidates.iter().any(|candidate| {
+            encoding_from_whatwg_label(&candidate.encoding)
+                .and_then(|encoding| encoding.whatwg_name())
+                == Some(canonical)
+        }) {
+            continue;
+        }
+
+        // 自动检测时乱码过多而被排除的编码，单独检测一次
+        let settings = NormalizerSettings {
+            include_encodings: vec![encoding.to_string()],
+            ..Default::default()
+        };
+
+        let confidence = from_bytes(&buffer, Some(settings))
+            .get_best()
+            .map(|item| 1.0 - item.chaos() as f64)
+            .unwrap_or(0.0);
+
+        candidates.push(EncodingCandidate {
+            encoding: encoding.to_string(),
+            confidence,
+            sample: sample_text(&buffer, encoding),
+        });
+    }
+
+    // 可信度相同（如都未被自动检测选中）时，能够正常解码的编码排在前面
+    candidates.sort_by(|a, b| {
+        b.confidence.total_cmp(&a.confidence).then_with(|| {
+            is_decodable(&buffer, &b.encoding).cmp(&is_decodable(&buffer, &a.encoding))
+        })
+    });
+
+    Ok(candidates)
+}
+
+// 文件开头按指定编码解码后的一段文本，末尾被截断的字符不显示
+fn sample_text(buffer: &[u8], encoding: &str) -> String {
+    let sample = &buffer[..buffer.len().min(SAMPLE_BYTES)];
+
+    let text = decode(sample, encoding, DecoderTrap::Replace, false, false).unwrap_or_default();
+
+    let mut chars: Vec<char> = text.trim_start_matches('\u{feff}').chars().collect();
+
+    if sample.len() < buffer.len() {
+        while chars.last() == Some(&'\u{fffd}') {
+            chars.pop();
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+// 文件开头能否按该编码无错误地解码
+fn is_decodable(buffer: &[u8], encoding: &str) -> bool {
+    let sample = &buffer[..buffer.len().min(SAMPLE_BYTES)];
+
+    decode(sample, encoding, DecoderTrap::Strict, true, true).is_ok()
+}
+
+// 是否为可以识别的编码名称
+pub fn is_known_encoding(encoding: &str) -> bool {
+    encoding_from_whatwg_label(encoding).is_some()
+}
@@ -0,0 +1,246 @@
+use unicode_linebreak::linebreaks;
+use unicode_width::UnicodeWidthChar;
+
+// 禁则：不能出现在行首的字符（句读、后括号、后引号、省略号、破折号等）
+const LINE_START_PROHIBITED: &str =
+    "，。、；：？！）］｝〕〉》」』】〙〗〟”’…—～·・‥ー々〻ヽヾゝゞぁぃぅぇぉっゃゅょゎァィゥェォッャュョヮヵヶ,.;:?!)]}%";
+
+// 禁则：不能出现在行尾的字符（前括号、前引号等）
+const LINE_END_PROHIBITED: &str = "（［｛〔〈《「『【〘〖〝“‘([{";
+
+// 段首缩进使用的全角空格
+const INDENT: char = '\u{3000}';
+
+// 按字数排版时，行尾悬挂标点最多超出的宽度（两个全角字符）
+const MAX_OVERFLOW: usize = 4;
+
+// 为满足禁则，最多从上一行移到下一行的字符数
+const MAX_UNDERFLOW: usize = 3;
+
+// 按显示宽度将段落折成多行，全角字符宽度为 2，半角字符宽度为 1
+// 引号、省略号、破折号等宽度不确定的字符在中文字体中按全角显示，同样计为 2
+// line_size 是每行的中文字数，与按字符数排版时保存的设置含义一致，两个半角字符占一个字的位置
+pub fn wrap_paragraph(paragraph: &str, line_size: usize) -> Vec<String> {
+    wrap_by_width(
+        paragraph,
+        (line_size.max(1) * 2) as f64,
+        MAX_OVERFLOW as f64,
+        &|char| char.width_cjk().unwrap_or(0) as f64,
+    )
+}
+
+// 按给定的字符宽度将段落折成多行，每行宽度不超过 line_width
+// 只在 Unicode 允许断行且符合禁则的位置换行，英文单词不会被拆开，单个单词超过一行时才强制切分
+// 行首禁则字符可以悬挂在上一行行尾（最多超出 max_overflow），行尾禁则字符移到下一行（稍微不足）
+pub fn wrap_by_width(
+    paragraph: &str,
+    line_width: f64,
+    max_overflow: f64,
+    char_width: &dyn Fn(char) -> f64,
+) -> Vec<String> {
+    let width = |text: &str| text.chars().map(char_width).sum::<f64>();
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut start = 0;
+
+    for (end, _) in linebreaks(paragraph) {
+        if end < paragraph.len() && !can_break(&paragraph[..end], &paragraph[end..]) {
+            continue;
+        }
+
+        // 段首的全角空格缩进与后面的文字放在一起，不单独成行
+        if end < paragraph.len() && paragraph[..end].trim_start_matches(INDENT).is_empty() {
+            continue;
+        }
+
+        let segment = &paragraph[start..end];
+        start = end;
+
+        // 行尾的空白不占宽度
+        let total_width = width(&line) + width(segment.trim_end());
+
+        // 片段末尾的行首禁则字符可以悬挂在行尾
+        let body_width = width(&line) + width(trim_prohibited_end(segment));
+
+        if total_width <= line_width
+            || (body_width <= line_width && total_width <= line_width + max_overflow)
+        {
+            line.push_str(segment);
+            continue;
+        }
+
+        if !line.trim().is_empty() {
+            lines.push(line.trim_end().to_string());
+        }
+        line = String::new();
+
+        if width(segment.trim_end()) <= line_width {
+            line.push_str(segment);
+            continue;
+        }
+
+        // 超长的片段按字符切分
+        for char in segment.chars() {
+            let line_width_with_char = width(&line) + char_width(char);
+
+            if line.is_empty() || line_width_with_char <= line_width || char.is_whitespace() {
+                line.push(char);
+                continue;
+            }
+
+            if LINE_START_PROHIBITED.contains(char)
+                && line_width_with_char <= line_width + max_overflow
+            {
+                line.push(char);
+                continue;
+            }
+
+            let carry = carry_to_next_line(&mut line, char);
+            lines.push(std::mem::take(&mut line));
+            line = carry;
+            line.push(char);
+        }
+    }
+
+    if !line.trim().is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines
+}
+
+// 在 before 和 after 之间断行是否符合禁则
+fn can_break(before: &str, after: &str) -> bool {
+    let last = before.chars().next_back();
+    let first = after.chars().next();
+
+    !last.is_some_and(|char| LINE_END_PROHIBITED.contains(char))
+        && !first.is_some_and(|char| LINE_START_PROHIBITED.contains(char))
+}
+
+// 去掉末尾的空白和行首禁则字符
+fn trim_prohibited_end(segment: &str) -> &str {
+    segment
+        .trim_end_matches(|char: char| char.is_whitespace() || LINE_START_PROHIBITED.contains(char))
+}
+
+// 强制切分时，从行尾取出若干字符移到下一行，使断行处符合禁则，找不到合适位置时直接切分
+fn carry_to_next_line(line: &mut String, next: char) -> String {
+    let mut carry = String::new();
+
+    for _ in 0..MAX_UNDERFLOW {
+        let after = format!("{carry}{next}");
+        if can_break(line, &after) {
+            return carry;
+        }
+
+        if line.chars().count() <= 1 {
+            break;
+        }
+
+        if let Some(char) = line.pop() {
+            carry.insert(0, char);
+        }
+    }
+
+    if can_break(line, &format!("{carry}{next}")) {
+        return carry;
+    }
+
+    line.push_str(&carry);
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_cjk_by_character_count() {
+        assert_eq!(
+            wrap_paragraph("天地玄黄宇宙洪荒日月盈昃", 4),
+            ["天地玄黄", "宇宙洪荒", "日月盈昃"]
+        );
+    }
+
+    #[test]
+    fn half_width_characters_take_half_a_character() {
+        assert_eq!(
+            wrap_paragraph("The quick brown fox jumps over the lazy dog.", 6),
+            ["The quick", "brown fox", "jumps over", "the lazy dog."]
+        );
+        assert_eq!(
+            wrap_paragraph("Supercalifragilisticexpialidocious", 5),
+            ["Supercalif", "ragilistic", "expialidoc", "ious"]
+        );
+    }
+
+    #[test]
+    fn hangs_line_start_punctuation() {
+        assert_eq!(
+            wrap_paragraph("天地玄黄。宇宙洪荒。", 4),
+            ["天地玄黄。", "宇宙洪荒。"]
+        );
+    }
+
+    #[test]
+    fn keeps_kinsoku_rules() {
+        let text = "他抬起头，看着远方说道：「今天的天气真好啊！」她笑了笑……没有回答。";
+
+        for line_size in 2..20 {
+            let lines = wrap_paragraph(text, line_size);
+
+            assert_eq!(lines.concat(), text);
+
+            for (index, line) in lines.iter().enumerate() {
+                let first = line.chars().next().unwrap();
+                let last = line.chars().next_back().unwrap();
+
+                assert!(
+                    index == 0 || !LINE_START_PROHIBITED.contains(first),
+                    "{lines:?}"
+                );
+                assert!(
+                    index + 1 == lines.len() || !LINE_END_PROHIBITED.contains(last),
+                    "{lines:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ambiguous_punctuation_is_full_width() {
+        let width = |line: &str| {
+            line.chars()
+                .map(|c| c.width_cjk().unwrap_or(0))
+                .sum::<usize>()
+        };
+
+        for line_size in 1..6 {
+            for line in wrap_paragraph("他说：“……”", line_size) {
+                assert!(width(trim_prohibited_end(&line)) <= line_size * 2, "{line}");
+                assert!(width(&line) <= line_size * 2 + MAX_OVERFLOW, "{line}");
+            }
+        }
+
+        assert_eq!(wrap_paragraph("他说：“……”", 3), ["他", "说：", "“……”"]);
+    }
+
+    #[test]
+    fn keeps_leading_indent() {
+        let text = "\u{3000}\u{3000}天地玄黄宇宙洪荒";
+
+        for line_size in 1..6 {
+            let lines = wrap_paragraph(text, line_size);
+
+            assert_eq!(lines.concat(), text);
+            assert!(lines[0].starts_with("\u{3000}\u{3000}"), "{lines:?}");
+        }
+
+        assert_eq!(
+            wrap_paragraph(text, 4),
+            ["\u{3000}\u{3000}天地", "玄黄宇宙", "洪荒"]
+        );
+    }
+}
@@ -1,34 +1,46 @@
-use crate::db::{model::Novel, Db};
+use sqlx::SqliteConnection;
 
-// 新增小说
+use crate::{
+    db::{
+        model::{ChapterRule, Novel, NovelMetadataUpdate, NovelSource, ReplaceRule},
+        Db,
+    },
+    utils::reader::{NovelMetadata, ReadAnchor},
+};
+
+// 新增小说，返回小说 id；在事务中执行，以便文件落盘失败时回滚
 pub async fn add_novel(
-    db: &Db,
+    db: &mut SqliteConnection,
     title: &str,
-    cover: Option<Vec<u8>>,
-    author: Option<String>,
-    description: Option<String>,
+    metadata: NovelMetadata,
     path: &str,
     file_size: i64,
-) -> Result<(), String> {
+    content_hash: &str,
+    source_path: &str,
+) -> Result<i64, String> {
     let sql = r#"
         INSERT INTO novel (
-            title, cover, author, description, path, read_position, read_progress, file_size
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            title, cover, author, description, path, read_position, read_progress, file_size,
+            page_count, content_hash, source_path
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
     "#;
-    sqlx::query(sql)
+    let result = sqlx::query(sql)
         .bind(title)
-        .bind(cover)
-        .bind(author)
-        .bind(description)
+        .bind(metadata.cover)
+        .bind(metadata.author)
+        .bind(metadata.description)
         .bind(path)
         .bind(0)
         .bind(0)
         .bind(file_size)
+        .bind(metadata.page_count)
+        .bind(content_hash)
+        .bind(source_path)
         .execute(db)
         .await
         .map_err(|e| format!("添加小说失败: {}", e))?;
 
-    Ok(())
+    Ok(result.last_insert_rowid())
 }
 
 // 根据 id 获取小说
@@ -58,10 +70,20 @@ pub async fn save_novel(
     novel_id: i64,
     read_position: i64,
     read_progress: f64,
+    read_anchor: &ReadAnchor,
 ) -> Result<(), String> {
-    sqlx::query("UPDATE novel SET read_position = ?, read_progress = ? WHERE id = ?")
+    let sql = r#"
+        UPDATE novel SET
+            read_position = ?, read_progress = ?, read_chapter = ?, read_paragraph = ?,
+            read_offset = ?
+        WHERE id = ?
+    "#;
+    sqlx::query(sql)
         .bind(read_position)
         .bind(read_progress)
+        .bind(read_anchor.chapter as i64)
+        .bind(read_anchor.paragraph as i64)
+        .bind(read_anchor.offset as i64)
         .bind(novel_id)
         .execute(db)
         .await
@@ -70,6 +92,175 @@ pub async fn save_novel(
     Ok(())
 }
 
+// 替换小说的文件，保留阅读进度等信息；新文件的编码可能不同，恢复自动检测
+pub async fn replace_novel_file(
+    db: &mut SqliteConnection,
+    id: i64,
+    path: &str,
+    file_size: i64,
+    content_hash: &str,
+    source_path: &str,
+) -> Result<(), String> {
+    let sql = r#"
+        UPDATE novel
+        SET path = ?, file_size = ?, content_hash = ?, encoding = NULL, source_path = ?,
+            source_missing = 0
+        WHERE id = ?
+    "#;
+    sqlx::query(sql)
+        .bind(path)
+        .bind(file_size)
+        .bind(content_hash)
+        .bind(source_path)
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("替换小说文件失败: {}", e))?;
+
+    Ok(())
+}
+
+// 根据内容哈希查找小说
+pub async fn find_novel_by_content_hash(
+    db: &Db,
+    content_hash: &str,
+) -> Result<Option<Novel>, String> {
+    let novel = sqlx::query_as::<_, Novel>(
+        "SELECT * FROM novel WHERE content_hash = ? ORDER BY id LIMIT 1",
+    )
+    .bind(content_hash)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| format!("获取小说失败: {}", e))?;
+
+    Ok(novel)
+}
+
+// 获取尚未计算内容哈希的小说，旧版本导入的小说没有内容哈希
+pub async fn get_novels_without_content_hash(db: &Db) -> Result<Vec<Novel>, String> {
+    let novels = sqlx::query_as::<_, Novel>("SELECT * FROM novel WHERE content_hash IS NULL")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("获取小说列表失败: {}", e))?;
+
+    Ok(novels)
+}
+
+// 设置小说的内容哈希
+pub async fn set_novel_content_hash(
+    db: &Db,
+    novel_id: i64,
+    content_hash: &str,
+) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET content_hash = ? WHERE id = ?")
+        .bind(content_hash)
+        .bind(novel_id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置内容哈希失败: {}", e))?;
+
+    Ok(())
+}
+
+// 更新小说的书名、作者等信息
+pub async fn update_novel_metadata(
+    db: &Db,
+    id: i64,
+    metadata: NovelMetadataUpdate,
+) -> Result<(), String> {
+    let sql = r#"
+        UPDATE novel
+        SET title = ?, author = ?, description = ?, series = ?, language = ?
+        WHERE id = ?
+    "#;
+    sqlx::query(sql)
+        .bind(metadata.title)
+        .bind(metadata.author)
+        .bind(metadata.description)
+        .bind(metadata.series)
+        .bind(metadata.language)
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("更新小说信息失败: {}", e))?;
+
+    Ok(())
+}
+
+// 设置小说封面，为空时清除封面
+pub async fn set_novel_cover(db: &Db, id: i64, cover: Option<Vec<u8>>) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET cover = ? WHERE id = ?")
+        .bind(cover)
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置小说封面失败: {}", e))?;
+
+    Ok(())
+}
+
+// 获取记录了源文件的小说
+pub async fn get_novel_sources(db: &Db) -> Result<Vec<NovelSource>, String> {
+    let sources = sqlx::query_as::<_, NovelSource>(
+        "SELECT id, source_path, source_missing FROM novel WHERE source_path IS NOT NULL",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("获取小说源文件失败: {}", e))?;
+
+    Ok(sources)
+}
+
+// 设置小说的源文件及其是否已丢失
+pub async fn set_novel_source(
+    db: &Db,
+    novel_id: i64,
+    source_path: &str,
+    source_missing: bool,
+) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET source_path = ?, source_missing = ? WHERE id = ?")
+        .bind(source_path)
+        .bind(source_missing)
+        .bind(novel_id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置小说源文件失败: {}", e))?;
+
+    Ok(())
+}
+
+// 记录不再自动导入的源文件
+pub async fn add_ignored_source(db: &Db, path: &str) -> Result<(), String> {
+    sqlx::query("INSERT OR IGNORE INTO ignored_source (path) VALUES (?)")
+        .bind(path)
+        .execute(db)
+        .await
+        .map_err(|e| format!("记录忽略的源文件失败: {}", e))?;
+
+    Ok(())
+}
+
+// 获取不再自动导入的源文件
+pub async fn get_ignored_sources(db: &Db) -> Result<Vec<String>, String> {
+    let paths = sqlx::query_scalar::<_, String>("SELECT path FROM ignored_source")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("获取忽略的源文件失败: {}", e))?;
+
+    Ok(paths)
+}
+
+// 使用该文件路径的小说数量
+pub async fn count_novels_by_path(db: &Db, path: &str) -> Result<i64, String> {
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM novel WHERE path = ?")
+        .bind(path)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("获取小说失败: {}", e))?;
+
+    Ok(count)
+}
+
 // 根据 id 删除小说
 pub async fn delete_novel(db: &Db, id: i64) -> Result<(), String> {
     sqlx::query("DELETE FROM novel WHERE id = ?")
@@ -78,6 +269,18 @@ pub async fn delete_novel(db: &Db, id: i64) -> Result<(), String> {
         .await
         .map_err(|e| format!("删除小说失败: {}", e))?;
 
+    sqlx::query("DELETE FROM chapter_rule WHERE novel_id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("删除章节规则失败: {}", e))?;
+
+    sqlx::query("DELETE FROM replace_rule WHERE novel_id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("删除替换规则失败: {}", e))?;
+
     Ok(())
 }
 
@@ -112,3 +315,175 @@ pub async fn get_open_novel(db: &Db) -> Result<Novel, String> {
 
     Ok(novel)
 }
+
+// 设置小说使用的章节规则预设
+pub async fn set_chapter_rule_presets(
+    db: &Db,
+    novel_id: i64,
+    presets: Option<String>,
+) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET chapter_rule_presets = ? WHERE id = ?")
+        .bind(presets)
+        .bind(novel_id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置章节规则预设失败: {}", e))?;
+
+    Ok(())
+}
+
+// 设置小说的文本编码，为空时自动检测
+pub async fn set_novel_encoding(
+    db: &Db,
+    novel_id: i64,
+    encoding: Option<String>,
+) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET encoding = ? WHERE id = ?")
+        .bind(encoding)
+        .bind(novel_id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置小说编码失败: {}", e))?;
+
+    Ok(())
+}
+
+// 设置小说单独使用的简繁转换，为空时使用全局设置
+pub async fn set_novel_chinese_conversion(
+    db: &Db,
+    novel_id: i64,
+    conversion: Option<String>,
+) -> Result<(), String> {
+    sqlx::query("UPDATE novel SET chinese_conversion = ? WHERE id = ?")
+        .bind(conversion)
+        .bind(novel_id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置简繁转换失败: {}", e))?;
+
+    Ok(())
+}
+
+// 获取小说的自定义章节规则
+pub async fn get_chapter_rules(db: &Db, novel_id: i64) -> Result<Vec<ChapterRule>, String> {
+    let rules = sqlx::query_as::<_, ChapterRule>(
+        "SELECT * FROM chapter_rule WHERE novel_id = ? ORDER BY id",
+    )
+    .bind(novel_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("获取章节规则失败: {}", e))?;
+
+    Ok(rules)
+}
+
+// 新增自定义章节规则
+pub async fn add_chapter_rule(
+    db: &Db,
+    novel_id: i64,
+    name: &str,
+    pattern: &str,
+) -> Result<(), String> {
+    sqlx::query("INSERT INTO chapter_rule (novel_id, name, pattern) VALUES (?1, ?2, ?3)")
+        .bind(novel_id)
+        .bind(name)
+        .bind(pattern)
+        .execute(db)
+        .await
+        .map_err(|e| format!("添加章节规则失败: {}", e))?;
+
+    Ok(())
+}
+
+// 根据 id 获取自定义章节规则
+pub async fn get_chapter_rule_by_id(db: &Db, id: i64) -> Result<ChapterRule, String> {
+    let rule = sqlx::query_as::<_, ChapterRule>("SELECT * FROM chapter_rule WHERE id = ?")
+        .bind(id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("获取章节规则失败: {}", e))?;
+
+    Ok(rule)
+}
+
+// 根据 id 删除自定义章节规则
+pub async fn delete_chapter_rule(db: &Db, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM chapter_rule WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("删除章节规则失败: {}", e))?;
+
+    Ok(())
+}
+
+// 获取对小说生效的替换规则，全局规则在前；novel_id 为空时只获取全局规则
+pub async fn get_replace_rules(db: &Db, novel_id: Option<i64>) -> Result<Vec<ReplaceRule>, String> {
+    let rules = sqlx::query_as::<_, ReplaceRule>(
+        "SELECT * FROM replace_rule WHERE novel_id IS NULL OR novel_id = ? ORDER BY novel_id IS NOT NULL, id",
+    )
+    .bind(novel_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("获取替换规则失败: {}", e))?;
+
+    Ok(rules)
+}
+
+// 新增替换规则，novel_id 为空时对所有小说生效
+pub async fn add_replace_rule(
+    db: &Db,
+    novel_id: Option<i64>,
+    name: &str,
+    kind: &str,
+    pattern: &str,
+    replacement: &str,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO replace_rule (novel_id, name, kind, pattern, replacement) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(novel_id)
+    .bind(name)
+    .bind(kind)
+    .bind(pattern)
+    .bind(replacement)
+    .execute(db)
+    .await
+    .map_err(|e| format!("添加替换规则失败: {}", e))?;
+
+    Ok(())
+}
+
+// 根据 id 获取替换规则
+pub async fn get_replace_rule_by_id(db: &Db, id: i64) -> Result<ReplaceRule, String> {
+    let rule = sqlx::query_as::<_, ReplaceRule>("SELECT * FROM replace_rule WHERE id = ?")
+        .bind(id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("获取替换规则失败: {}", e))?;
+
+    Ok(rule)
+}
+
+// 启用或停用替换规则
+pub async fn set_replace_rule_enabled(db: &Db, id: i64, enabled: bool) -> Result<(), String> {
+    sqlx::query("UPDATE replace_rule SET enabled = ? WHERE id = ?")
+        .bind(enabled)
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("设置替换规则失败: {}", e))?;
+
+    Ok(())
+}
+
+// 根据 id 删除替换规则
+pub async fn delete_replace_rule(db: &Db, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM replace_rule WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| format!("删除替换规则失败: {}", e))?;
+
+    Ok(())
+}
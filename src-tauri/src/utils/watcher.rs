@@ -0,0 +1,213 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    constants::event::*,
+    db::Db,
+    utils::{
+        import::{self, DuplicateAction, ImportStatus},
+        sql,
+    },
+};
+
+// 扫描监视文件夹的间隔（秒）
+const WATCH_INTERVAL: u64 = 10;
+
+// 文件在这段时间内没有修改才导入，避免导入尚未下载或复制完成的文件（秒）
+const STABLE_DURATION: u64 = 5;
+
+// 定期扫描监视文件夹，导入新出现的小说，并标记源文件已被移除或重命名的小说
+// 使用轮询而不是文件系统通知：监视的文件夹可能位于网络驱动器或移动硬盘上，
+// 这些位置通常收不到通知；而且文件一段时间不再修改后才能导入，本来就需要再次检查
+pub struct FolderWatcher {
+    stopped: Option<Arc<AtomicBool>>,
+}
+
+// 多次扫描之间保留的状态
+#[derive(Default)]
+struct ScanState {
+    // 已导入或与已有小说重复而跳过的文件，不再重复尝试
+    seen: HashSet<PathBuf>,
+    // 导入失败的文件及失败时的修改时间，文件再次被修改后才重新尝试
+    failed: HashMap<PathBuf, SystemTime>,
+}
+
+impl FolderWatcher {
+    pub fn new() -> Self {
+        Self { stopped: None }
+    }
+
+    pub fn start(&mut self, app_handle: &AppHandle, folders: Vec<String>) -> Result<(), String> {
+        self.stop();
+
+        if folders.is_empty() {
+            return Ok(());
+        }
+
+        let app_handle = app_handle.clone();
+
+        // 不直接中止任务，避免导入到一半时留下未入库的文件，而是在两次扫描之间检查是否已停止
+        let stopped = Arc::new(AtomicBool::new(false));
+        self.stopped = Some(stopped.clone());
+
+        tauri::async_runtime::spawn(async move {
+            let mut scan_state = ScanState::default();
+
+            log::info!(target: "FolderWatcher", "开始监视文件夹: {folders:?}");
+
+            while !stopped.load(Ordering::SeqCst) {
+                if let Err(e) = Self::scan(&app_handle, &folders, &mut scan_state, &stopped).await {
+                    log::error!(target: "FolderWatcher", "扫描监视文件夹失败: {e}");
+                }
+
+                sleep(Duration::from_secs(WATCH_INTERVAL)).await;
+            }
+
+            log::info!(target: "FolderWatcher", "停止监视文件夹");
+        });
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(stopped) = self.stopped.take() {
+            stopped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    async fn scan(
+        app_handle: &AppHandle,
+        folders: &[String],
+        scan_state: &mut ScanState,
+        stopped: &AtomicBool,
+    ) -> Result<(), String> {
+        let db = app_handle.state::<Db>();
+
+        let mut changed = false;
+
+        let sources = sql::get_novel_sources(&db).await?;
+
+        // 标记源文件已丢失的小说，文件恢复后取消标记
+        for source in &sources {
+            let source_path = Path::new(&source.source_path);
+
+            if !is_watched(source_path, folders) {
+                continue;
+            }
+
+            let missing = !source_path.exists();
+
+            if missing != (source.source_missing != 0) {
+                let path = &source.source_path;
+                log::info!(target: "FolderWatcher", "源文件丢失状态变化 {path}: {missing}");
+                sql::set_novel_source(&db, source.id, &source.source_path, missing).await?;
+                changed = true;
+            }
+        }
+
+        // 已导入的源文件与已从书库删除的源文件都不再导入
+        let known: HashSet<PathBuf> = sources
+            .iter()
+            .map(|source| PathBuf::from(&source.source_path))
+            .chain(
+                sql::get_ignored_sources(&db)
+                    .await?
+                    .into_iter()
+                    .map(PathBuf::from),
+            )
+            .collect();
+
+        let files = {
+            let folders = folders.to_vec();
+            tauri::async_runtime::spawn_blocking(move || import::collect_files(&folders, true))
+                .await
+                .map_err(|e| e.to_string())?
+        };
+
+        // 已被删除、移走或不再监视的文件不再记录
+        let present: HashSet<&PathBuf> = files.iter().collect();
+        scan_state.seen.retain(|file| present.contains(file));
+        scan_state.failed.retain(|file, _| present.contains(file));
+
+        for file in &files {
+            if stopped.load(Ordering::SeqCst) {
+                break;
+            }
+
+            if known.contains(file) || scan_state.seen.contains(file) {
+                continue;
+            }
+
+            let Some(modified) = stable_modified_time(file) else {
+                continue;
+            };
+
+            if scan_state.failed.get(file) == Some(&modified) {
+                continue;
+            }
+
+            let path = file.to_string_lossy().to_string();
+
+            match import::import_novel(app_handle, &db, &path, Some(DuplicateAction::Skip)).await {
+                Ok(result) if result.status == ImportStatus::Skipped => {
+                    scan_state.seen.insert(file.clone());
+
+                    // 内容相同的小说源文件已丢失时，视为源文件被重命名或移动到此处
+                    let novel = sql::get_novel_by_id(&db, result.novel_id).await?;
+
+                    if novel.source_path.is_none() || novel.source_missing != 0 {
+                        let title = &novel.title;
+                        log::info!(target: "FolderWatcher", "源文件已移动到 {path}: {title}");
+                        sql::set_novel_source(&db, novel.id, &path, false).await?;
+                        changed = true;
+                    }
+                }
+                Ok(result) => {
+                    scan_state.seen.insert(file.clone());
+
+                    let title = &result.title;
+                    log::info!(target: "FolderWatcher", "自动导入 {path}: {title}");
+                    changed = true;
+                }
+                Err(e) => {
+                    log::error!(target: "FolderWatcher", "自动导入失败 {path}: {e}");
+                    scan_state.failed.insert(file.clone(), modified);
+                }
+            }
+        }
+
+        if changed {
+            app_handle.emit(LIBRARY_CHANGE, ()).unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+fn is_watched(path: &Path, folders: &[String]) -> bool {
+    folders.iter().any(|folder| path.starts_with(folder))
+}
+
+// 文件最近没有被修改时返回其修改时间
+fn stable_modified_time(path: &Path) -> Option<SystemTime> {
+    let modified = path
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+
+    SystemTime::now()
+        .duration_since(modified)
+        .ok()
+        .filter(|elapsed| elapsed.as_secs() >= STABLE_DURATION)
+        .map(|_| modified)
+}
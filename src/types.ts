@@ -24,6 +24,172 @@ export interface Novel {
   updated_at: string;
   /** 是否打开 */
   is_open: 0 | 1;
+  /** 章节规则预设（以逗号分隔，为空时使用默认预设） */
+  chapter_rule_presets?: string;
+  /** 页数（仅 PDF） */
+  page_count?: number;
+  /** 阅读位置锚点：章节序号 */
+  read_chapter?: number;
+  /** 阅读位置锚点：章节内的段落序号 */
+  read_paragraph?: number;
+  /** 阅读位置锚点：段落内的字符偏移 */
+  read_offset?: number;
+  /** 手动指定的文本编码（为空时自动检测） */
+  encoding?: string;
+  /** 单独设置的简繁转换（为空时使用全局设置） */
+  chinese_conversion?: string;
+  /** 内容哈希，用于导入时检测重复 */
+  content_hash?: string;
+  /** 导入时的源文件路径 */
+  source_path?: string;
+  /** 源文件是否已被移除或重命名 */
+  source_missing: 0 | 1;
+  /** 系列 */
+  series?: string;
+  /** 语言 */
+  language?: string;
+}
+
+export interface NovelMetadataUpdate {
+  /** 小说标题 */
+  title: string;
+  /** 小说作者 */
+  author?: string;
+  /** 小说描述 */
+  description?: string;
+  /** 系列 */
+  series?: string;
+  /** 语言 */
+  language?: string;
+}
+
+export interface EncodingCandidate {
+  /** 编码名称 */
+  encoding: string;
+  /** 可信度（0 ~ 1） */
+  confidence: number;
+  /** 按该编码解码的示例文本 */
+  sample: string;
+}
+
+/** 导入的小说与已有的小说重复时的处理方式 */
+export type DuplicateAction = 'skip' | 'replace' | 'add';
+
+export type ImportStatus = 'added' | 'skipped' | 'replaced' | 'duplicate';
+
+export interface ImportResult {
+  /** 导入结果，duplicate 表示发现重复且未导入 */
+  status: ImportStatus;
+  /** 新增的小说，或与导入的文件内容相同的已有小说 ID */
+  novel_id: number;
+  /** 小说标题 */
+  title: string;
+}
+
+export interface ImportProgress {
+  /** 当前导入的文件序号（从 1 开始） */
+  current: number;
+  /** 文件总数 */
+  total: number;
+  /** 当前导入的文件路径 */
+  path: string;
+}
+
+export interface ImportSucceeded {
+  /** 文件路径 */
+  path: string;
+  /** 导入结果 */
+  result: ImportResult;
+}
+
+export interface ImportFailed {
+  /** 文件路径 */
+  path: string;
+  /** 失败原因 */
+  error: string;
+}
+
+export interface ImportSummary {
+  /** 文件总数 */
+  total: number;
+  /** 新增数量 */
+  added: number;
+  /** 跳过数量 */
+  skipped: number;
+  /** 替换数量 */
+  replaced: number;
+  /** 重复且未导入的数量 */
+  duplicate: number;
+  /** 失败数量 */
+  failed: number;
+  /** 是否被取消 */
+  cancelled: boolean;
+}
+
+/* ---------------------------------- 章节规则 ---------------------------------- */
+export interface ChapterRulePreset {
+  /** 预设标识 */
+  key: string;
+  /** 预设名称 */
+  name: string;
+  /** 章节正则 */
+  pattern: string;
+  /** 是否默认启用 */
+  is_default: boolean;
+}
+
+export interface ChapterRule {
+  /** 规则 ID */
+  id: number;
+  /** 小说 ID */
+  novel_id: number;
+  /** 规则名称 */
+  name: string;
+  /** 章节正则 */
+  pattern: string;
+  /** 添加时间 */
+  created_at: string;
+}
+
+export type ReplaceRuleKind = 'literal' | 'regex' | 'delete_line';
+
+export interface ReplaceRule {
+  /** 规则 ID */
+  id: number;
+  /** 小说 ID（为空时对所有小说生效） */
+  novel_id?: number;
+  /** 规则名称 */
+  name: string;
+  /** 规则类型 */
+  kind: ReplaceRuleKind;
+  /** 匹配内容，正则或删除段落规则为正则 */
+  pattern: string;
+  /** 替换内容 */
+  replacement: string;
+  /** 是否启用 */
+  enabled: number;
+  /** 添加时间 */
+  created_at: string;
+}
+
+export interface CleanupPreview {
+  /** 修改前的段落 */
+  before: string;
+  /** 修改后的段落（为空时段落被删除） */
+  after?: string;
+}
+
+export interface ChapterPreview {
+  /** 章节序号 */
+  index: number;
+  /** 章节标题 */
+  title: string;
+  /** 起始行号 */
+  start_line: number;
+  /** 章节行数 */
+  line_count: number;
+  /** 章节字数 */
+  char_count: number;
 }
 
 /* ----------------------------------- 阅读器 ---------------------------------- */
@@ -44,6 +210,19 @@ export interface Reader {
   total_lines: number;
 }
 
+export interface Line {
+  /** 是否为章节标题 */
+  is_chapter: boolean;
+  /** 行内容 */
+  content: string;
+  /** 该行在所属段落中的字符偏移 */
+  offset: number;
+  /** 是否为段落的第一行 */
+  is_paragraph_start: boolean;
+  /** 是否为段落的最后一行 */
+  is_paragraph_end: boolean;
+}
+
 export interface Chapter {
   index: number;
   title: string;
@@ -61,6 +240,8 @@ export interface Config {
   language: string;
   /** 主题 */
   theme: string;
+  /** 监视的文件夹 */
+  watched_folders: string[];
   /* ---------------------------------- 系统设置 ---------------------------------- */
   /** 是否显示 Dock 图标 */
   dock_visibility: boolean;
@@ -71,6 +252,14 @@ export interface Config {
   /* ---------------------------------- 阅读设置 ---------------------------------- */
   /** 每页字数 */
   line_size: number;
+  /** 是否按阅读器窗口宽度排版 */
+  fit_to_window: boolean;
+  /** 简繁转换 */
+  chinese_conversion: string;
+  /** 是否按段落翻页 */
+  paragraph_navigation: boolean;
+  /** 阅读器窗口中文本区域的宽度 */
+  line_width: number;
   /** 字体大小 */
   font_size: number;
   /** 字体 */
@@ -120,4 +309,9 @@ export enum CustomEvent {
   UpdateCheckStatusChange = 'update-check-status-changed',
   UpdateProgressChange = 'update-progress-change',
   UpdateFinished = 'update-finished',
+  ImportProgressChange = 'import-progress-change',
+  ImportSucceeded = 'import-succeeded',
+  ImportFailed = 'import-failed',
+  ImportFinished = 'import-finished',
+  LibraryChange = 'library-change',
 }
@@ -1,11 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
-import { useRequest } from 'ahooks';
+import { useDebounceFn, useRequest } from 'ahooks';
 import { Minus, X } from 'lucide-react';
-import { useEffect, useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { cn } from '@/lib/utils';
-import { Config, CustomEvent, Reader } from '@/types';
+import { Config, CustomEvent, Line, Reader } from '@/types';
 
 export default function ReaderWindow() {
   const {
@@ -20,8 +20,9 @@ export default function ReaderWindow() {
     invoke<string>('get_reading_mode'),
   );
 
-  const { data: line, refresh: refreshLine } = useRequest(() =>
-    invoke<string>('get_line'),
+  // 按段落翻页时为从当前行到段落结束的各行
+  const { data: lines, refresh: refreshLine } = useRequest(() =>
+    invoke<Line[]>('get_line'),
   );
 
   const { data: config, refresh: refreshConfig } = useRequest(() =>
@@ -69,6 +70,34 @@ export default function ReaderWindow() {
     };
   }, [win]);
 
+  // 按窗口宽度排版时，上报文本区域的宽度，窗口大小变化后由后端重新排版
+  const containerRef = useRef<HTMLDivElement>(null);
+
+  const { run: reportLineWidth } = useDebounceFn(
+    (lineWidth: number) => invoke('set_line_width', { lineWidth }),
+    { wait: 300 },
+  );
+
+  useEffect(() => {
+    const container = containerRef.current;
+
+    if (!container || !config?.fit_to_window) return;
+
+    const observer = new ResizeObserver(() => {
+      const style = getComputedStyle(container);
+      const lineWidth =
+        container.clientWidth -
+        parseFloat(style.paddingLeft) -
+        parseFloat(style.paddingRight);
+
+      reportLineWidth(lineWidth);
+    });
+
+    observer.observe(container);
+
+    return () => observer.disconnect();
+  }, [config?.fit_to_window, reportLineWidth]);
+
   const computedStyle: React.CSSProperties = {
     fontSize: `${config?.font_size}px`,
     fontFamily: config?.font_family,
@@ -85,12 +114,16 @@ export default function ReaderWindow() {
           'fixed h-screen w-screen select-none border border-dashed border-transparent overflow-hidden p-1 rounded',
           isFocus && 'border-black/[0.2] bg-white',
         )}
+        ref={containerRef}
         style={computedStyle}
         data-tauri-drag-region
       >
         {!reader ? <p>请从托盘菜单打开一本小说</p> : null}
-        {reader && line ? line : null}
-        {reader && reader.read_progress === 100 && !line ? '（已读完）' : null}
+        {reader &&
+          lines?.map((line) => <p key={line.offset}>{line.content}</p>)}
+        {reader && reader.read_progress === 100 && !lines?.length
+          ? '（已读完）'
+          : null}
         {reader && isFocus && (
           <div className="text-center text-sm text-muted-foreground mt-1">
             <p>{reader?.current_chapter.title}</p>
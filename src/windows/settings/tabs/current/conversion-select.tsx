@@ -0,0 +1,58 @@
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { CHINESE_CONVERSION_OPTIONS } from '../settings/components/chinese-conversion-selector';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Novel } from '@/types';
+
+// 跟随全局设置时，小说的简繁转换为空
+const FOLLOW_GLOBAL = 'global';
+
+interface ConversionSelectProps {
+  novel: Novel;
+  refresh: () => void;
+}
+
+const ConversionSelect: React.FC<ConversionSelectProps> = (props) => {
+  const { novel, refresh } = props;
+
+  const handleChange = (value: string) => {
+    const conversion = value === FOLLOW_GLOBAL ? null : value;
+
+    toast.promise(
+      invoke('set_novel_chinese_conversion', { id: novel.id, conversion }),
+      {
+        loading: '转换中...',
+        success: '设置成功！',
+        error: (e) => `设置失败：${e}`,
+        finally: () => refresh(),
+      },
+    );
+  };
+
+  return (
+    <Select
+      value={novel.chinese_conversion ?? FOLLOW_GLOBAL}
+      onValueChange={handleChange}
+    >
+      <SelectTrigger className="w-36">
+        <SelectValue />
+      </SelectTrigger>
+      <SelectContent>
+        <SelectItem value={FOLLOW_GLOBAL}>跟随全局设置</SelectItem>
+        {CHINESE_CONVERSION_OPTIONS.map((option) => (
+          <SelectItem key={option.value} value={option.value}>
+            {option.label}
+          </SelectItem>
+        ))}
+      </SelectContent>
+    </Select>
+  );
+};
+
+export default ConversionSelect;
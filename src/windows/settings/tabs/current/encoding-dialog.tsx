@@ -0,0 +1,101 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useRequest } from 'ahooks';
+import { toast } from 'sonner';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from '@/components/ui/dialog';
+import { ScrollArea } from '@/components/ui/scroll-area';
+import { ScrollMask } from '@/components/ui/scroll-mask';
+import { cn } from '@/lib/utils';
+import { EncodingCandidate, Novel } from '@/types';
+
+// 可以手动指定编码的纯文本格式
+const PLAIN_TEXT_EXTENSIONS = ['txt', 'md', 'markdown', 'html', 'htm'];
+
+interface EncodingDialogProps {
+  novel: Novel;
+  refresh: () => void;
+}
+
+const EncodingDialog: React.FC<EncodingDialogProps> = (props) => {
+  const { novel, refresh } = props;
+
+  const extension = novel.path.split('.').pop()?.toLowerCase() ?? '';
+
+  const { data: candidates, run } = useRequest(
+    () =>
+      invoke<EncodingCandidate[]>('get_encoding_candidates', { id: novel.id }),
+    { manual: true },
+  );
+
+  const handleSelect = (encoding: string | null) => {
+    toast.promise(invoke('set_novel_encoding', { id: novel.id, encoding }), {
+      loading: '重新解码中...',
+      success: encoding ? `已指定编码 ${encoding}` : '已恢复自动检测',
+      error: (e) => `设置失败：${e}`,
+      finally: () => refresh(),
+    });
+  };
+
+  if (!PLAIN_TEXT_EXTENSIONS.includes(extension)) return null;
+
+  return (
+    <Dialog onOpenChange={(open) => open && run()}>
+      <DialogTrigger asChild>
+        <Button variant="outline">文本编码</Button>
+      </DialogTrigger>
+      <DialogContent className="h-4/5 flex flex-col overflow-hidden">
+        <DialogHeader>
+          <DialogTitle>文本编码</DialogTitle>
+          <DialogDescription>
+            正文出现乱码时，选择示例文本显示正常的编码
+          </DialogDescription>
+        </DialogHeader>
+        <ScrollArea className="flex-1 h-0 relative">
+          <div className="space-y-2">
+            {candidates?.map((candidate) => (
+              <div
+                key={candidate.encoding}
+                className={cn(
+                  'p-3 rounded-lg border cursor-pointer transition-colors hover:bg-accent space-y-1',
+                  candidate.encoding === novel.encoding && 'border-primary',
+                )}
+                onClick={() => handleSelect(candidate.encoding)}
+              >
+                <div className="flex items-center justify-between text-sm">
+                  <span className="font-medium">{candidate.encoding}</span>
+                  <Badge variant="secondary">
+                    {(candidate.confidence * 100).toFixed(0)}%
+                  </Badge>
+                </div>
+                <p className="text-xs text-muted-foreground line-clamp-2 break-all">
+                  {candidate.sample}
+                </p>
+              </div>
+            ))}
+          </div>
+          <ScrollMask />
+        </ScrollArea>
+        <DialogFooter>
+          <Button
+            variant="outline"
+            disabled={!novel.encoding}
+            onClick={() => handleSelect(null)}
+          >
+            恢复自动检测
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
+
+export default EncodingDialog;
@@ -3,6 +3,9 @@ import { listen } from '@tauri-apps/api/event';
 import { Package } from 'lucide-react';
 import { useCallback, useEffect, useState } from 'react';
 import ChapterDialog from './chapter-dialog';
+import ConversionSelect from './conversion-select';
+import EncodingDialog from './encoding-dialog';
+import ReplaceRuleDialog from './replace-rule-dialog';
 import { Badge } from '@/components/ui/badge';
 import { Button } from '@/components/ui/button';
 import {
@@ -91,6 +94,9 @@ const CurrentTab: React.FC = () => {
           继续阅读
         </Button>
         <ChapterDialog novel={novel} reader={reader} refresh={fetchData} />
+        <EncodingDialog novel={novel} refresh={fetchData} />
+        <ReplaceRuleDialog novel={novel} refresh={fetchData} />
+        <ConversionSelect novel={novel} refresh={fetchData} />
       </CardFooter>
     </Card>
   );
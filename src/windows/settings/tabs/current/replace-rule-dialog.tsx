@@ -0,0 +1,259 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useRequest } from 'ahooks';
+import { Trash2 } from 'lucide-react';
+import { useState } from 'react';
+import { toast } from 'sonner';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from '@/components/ui/dialog';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { ScrollArea } from '@/components/ui/scroll-area';
+import { ScrollMask } from '@/components/ui/scroll-mask';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Separator } from '@/components/ui/separator';
+import { Switch } from '@/components/ui/switch';
+import { CleanupPreview, Novel, ReplaceRule, ReplaceRuleKind } from '@/types';
+
+const KIND_LABELS: Record<ReplaceRuleKind, string> = {
+  literal: '文本替换',
+  regex: '正则替换',
+  delete_line: '删除段落',
+};
+
+interface ReplaceRuleDialogProps {
+  novel: Novel;
+  refresh: () => void;
+}
+
+const ReplaceRuleDialog: React.FC<ReplaceRuleDialogProps> = (props) => {
+  const { novel, refresh } = props;
+
+  const [name, setName] = useState('');
+  const [kind, setKind] = useState<ReplaceRuleKind>('literal');
+  const [pattern, setPattern] = useState('');
+  const [replacement, setReplacement] = useState('');
+  // 是否只对当前小说生效
+  const [novelOnly, setNovelOnly] = useState(false);
+  const [previews, setPreviews] = useState<CleanupPreview[]>();
+
+  const { data: rules, run: fetchRules } = useRequest(
+    () => invoke<ReplaceRule[]>('get_replace_rules', { novelId: novel.id }),
+    { manual: true },
+  );
+
+  // 规则变化后重新解析小说，同时刷新规则列表与阅读信息
+  const withReload = (promise: Promise<unknown>, success: string) => {
+    toast.promise(promise, {
+      loading: '重新解析中...',
+      success,
+      error: (e) => `操作失败：${e}`,
+      finally: () => {
+        fetchRules();
+        refresh();
+      },
+    });
+  };
+
+  const handlePreview = async () => {
+    try {
+      const previews = await invoke<CleanupPreview[]>('preview_replace_rule', {
+        kind,
+        pattern,
+        replacement,
+      });
+      setPreviews(previews);
+    } catch (e) {
+      toast.error(`预览失败：${e}`);
+    }
+  };
+
+  const handleAdd = () => {
+    const promise = invoke('add_replace_rule', {
+      novelId: novelOnly ? novel.id : null,
+      name: name || pattern,
+      kind,
+      pattern,
+      replacement: kind === 'delete_line' ? '' : replacement,
+    }).then(() => {
+      setName('');
+      setPattern('');
+      setReplacement('');
+      setPreviews(undefined);
+    });
+
+    withReload(promise, '添加成功！');
+  };
+
+  const handleToggle = (rule: ReplaceRule, enabled: boolean) => {
+    withReload(
+      invoke('set_replace_rule_enabled', { id: rule.id, enabled }),
+      enabled ? '已启用' : '已停用',
+    );
+  };
+
+  const handleDelete = (rule: ReplaceRule) => {
+    withReload(invoke('delete_replace_rule', { id: rule.id }), '删除成功！');
+  };
+
+  return (
+    <Dialog onOpenChange={(open) => open && fetchRules()}>
+      <DialogTrigger asChild>
+        <Button variant="outline">替换规则</Button>
+      </DialogTrigger>
+      <DialogContent className="h-4/5 flex flex-col overflow-hidden">
+        <DialogHeader>
+          <DialogTitle>替换规则</DialogTitle>
+          <DialogDescription>
+            清理水印、广告等内容，规则按顺序执行，全局规则先于本书规则
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="grid grid-cols-2 gap-3">
+          <div className="space-y-2">
+            <Label>规则名称</Label>
+            <Input
+              placeholder="留空时使用匹配内容"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label>规则类型</Label>
+            <Select
+              value={kind}
+              onValueChange={(value) => setKind(value as ReplaceRuleKind)}
+            >
+              <SelectTrigger className="w-full">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {Object.entries(KIND_LABELS).map(([value, label]) => (
+                  <SelectItem key={value} value={value}>
+                    {label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="space-y-2">
+            <Label>{kind === 'literal' ? '查找内容' : '正则表达式'}</Label>
+            <Input
+              value={pattern}
+              onChange={(e) => setPattern(e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label>替换为</Label>
+            <Input
+              placeholder={kind === 'regex' ? '可使用 $1 引用分组' : ''}
+              disabled={kind === 'delete_line'}
+              value={replacement}
+              onChange={(e) => setReplacement(e.target.value)}
+            />
+          </div>
+          <div className="col-span-2 flex items-center justify-between">
+            <div className="flex items-center gap-2">
+              <Switch checked={novelOnly} onCheckedChange={setNovelOnly} />
+              <Label>仅对本书生效</Label>
+            </div>
+            <div className="flex gap-2">
+              <Button
+                variant="outline"
+                disabled={!pattern}
+                onClick={handlePreview}
+              >
+                预览当前章节
+              </Button>
+              <Button disabled={!pattern} onClick={handleAdd}>
+                添加
+              </Button>
+            </div>
+          </div>
+        </div>
+
+        <Separator />
+
+        <ScrollArea className="flex-1 h-0 relative">
+          {previews ? (
+            <div className="space-y-2">
+              <div className="flex items-center justify-between text-sm">
+                <span className="text-muted-foreground">
+                  当前章节共 {previews.length} 处修改
+                </span>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => setPreviews(undefined)}
+                >
+                  返回规则列表
+                </Button>
+              </div>
+              {previews.map((preview, index) => (
+                <div key={index} className="p-3 rounded-lg border text-sm">
+                  <p className="text-muted-foreground line-through break-all">
+                    {preview.before}
+                  </p>
+                  <p className="break-all">{preview.after ?? '（删除）'}</p>
+                </div>
+              ))}
+            </div>
+          ) : (
+            <div className="space-y-2">
+              {rules?.map((rule) => (
+                <div
+                  key={rule.id}
+                  className="flex items-center justify-between gap-3 p-3 rounded-lg border"
+                >
+                  <div className="w-0 flex-1 space-y-1">
+                    <div className="flex items-center gap-1 text-sm">
+                      <span className="truncate font-medium">{rule.name}</span>
+                      <Badge variant="secondary">
+                        {KIND_LABELS[rule.kind]}
+                      </Badge>
+                      <Badge variant="outline">
+                        {rule.novel_id ? '本书' : '全局'}
+                      </Badge>
+                    </div>
+                    <p className="text-xs text-muted-foreground truncate">
+                      {rule.pattern}
+                      {rule.kind !== 'delete_line' &&
+                        ` → ${rule.replacement || '（空）'}`}
+                    </p>
+                  </div>
+                  <Switch
+                    checked={!!rule.enabled}
+                    onCheckedChange={(enabled) => handleToggle(rule, enabled)}
+                  />
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    onClick={() => handleDelete(rule)}
+                  >
+                    <Trash2 size={14} />
+                  </Button>
+                </div>
+              ))}
+            </div>
+          )}
+          <ScrollMask />
+        </ScrollArea>
+      </DialogContent>
+    </Dialog>
+  );
+};
+
+export default ReplaceRuleDialog;
@@ -0,0 +1,48 @@
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { DuplicateAction, ImportResult } from '@/types';
+
+interface DuplicateDialogProps {
+  /** 发现重复的导入结果，为空时不显示 */
+  duplicate?: ImportResult;
+  onSelect: (action: DuplicateAction) => void;
+  onCancel: () => void;
+}
+
+const DuplicateDialog: React.FC<DuplicateDialogProps> = (props) => {
+  const { duplicate, onSelect, onCancel } = props;
+
+  return (
+    <Dialog open={!!duplicate} onOpenChange={(open) => !open && onCancel()}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>小说已存在</DialogTitle>
+          <DialogDescription>
+            书库中的《{duplicate?.title}》与导入的文件内容相同
+          </DialogDescription>
+        </DialogHeader>
+        <p className="text-sm text-muted-foreground">
+          替换会使用导入的文件，并保留原有的书名和阅读进度
+        </p>
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onSelect('skip')}>
+            跳过
+          </Button>
+          <Button variant="outline" onClick={() => onSelect('add')}>
+            仍然导入
+          </Button>
+          <Button onClick={() => onSelect('replace')}>替换</Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
+
+export default DuplicateDialog;
@@ -0,0 +1,119 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useEffect, useState } from 'react';
+import { toast } from 'sonner';
+import { Button } from '@/components/ui/button';
+import { Progress } from '@/components/ui/progress';
+import {
+  CustomEvent,
+  ImportFailed,
+  ImportProgress as Progression,
+  ImportSummary,
+} from '@/types';
+
+interface ImportProgressProps {
+  /** 是否已开始批量导入，开始后在收到进度事件前先显示准备中 */
+  importing: boolean;
+  setImporting: (importing: boolean) => void;
+  refresh: () => void;
+}
+
+const ImportProgress: React.FC<ImportProgressProps> = (props) => {
+  const { importing, setImporting, refresh } = props;
+
+  const [progress, setProgress] = useState<Progression>();
+  const [failures, setFailures] = useState<ImportFailed[]>([]);
+
+  useEffect(() => {
+    // 重新打开窗口时恢复导入中的状态
+    invoke<boolean>('is_importing').then(
+      (running) => running && setImporting(true),
+    );
+
+    const progressListener = listen<Progression>(
+      CustomEvent.ImportProgressChange,
+      (event) => {
+        setImporting(true);
+        setProgress(event.payload);
+      },
+    );
+
+    const succeededListener = listen(CustomEvent.ImportSucceeded, () =>
+      refresh(),
+    );
+
+    const failedListener = listen<ImportFailed>(
+      CustomEvent.ImportFailed,
+      (event) => setFailures((failures) => [...failures, event.payload]),
+    );
+
+    const finishedListener = listen<ImportSummary>(
+      CustomEvent.ImportFinished,
+      (event) => {
+        const summary = event.payload;
+        const message = `新增 ${summary.added} 本，跳过 ${summary.skipped + summary.duplicate} 本，替换 ${summary.replaced} 本，失败 ${summary.failed} 本`;
+
+        if (summary.cancelled) {
+          toast.warning(`导入已取消：${message}`);
+        } else if (summary.failed) {
+          toast.error(`导入完成：${message}`);
+        } else {
+          toast.success(`导入完成：${message}`);
+        }
+
+        setImporting(false);
+        setProgress(undefined);
+        setFailures([]);
+        refresh();
+      },
+    );
+
+    return () => {
+      progressListener.then((unListen) => unListen());
+      succeededListener.then((unListen) => unListen());
+      failedListener.then((unListen) => unListen());
+      finishedListener.then((unListen) => unListen());
+    };
+  }, []);
+
+  const handleCancel = () => {
+    invoke('cancel_import');
+  };
+
+  if (!importing) return null;
+
+  return (
+    <div className="border rounded-lg p-4 mb-4 space-y-2">
+      <div className="flex items-center justify-between gap-4">
+        <div className="w-0 flex-1 text-sm">
+          <div className="font-medium">
+            {progress
+              ? `正在导入 ${progress.current} / ${progress.total}`
+              : '正在准备导入...'}
+          </div>
+          <div className="text-xs text-muted-foreground truncate">
+            {progress?.path}
+          </div>
+        </div>
+        <Button variant="outline" size="sm" onClick={handleCancel}>
+          取消
+        </Button>
+      </div>
+      <Progress
+        value={progress ? (progress.current / progress.total) * 100 : 0}
+        className="h-1"
+      />
+      {failures.length > 0 && (
+        <div className="text-xs text-destructive space-y-1">
+          {failures.map((failure) => (
+            <div key={failure.path} className="truncate">
+              {failure.path}：{failure.error}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default ImportProgress;
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-dialog';
 import { useRequest } from 'ahooks';
 import { filesize } from 'filesize';
@@ -6,13 +7,18 @@ import {
   Book,
   File,
   FileText,
+  FolderOpen,
   HardDrive,
+  Library,
   MoreHorizontal,
   Plus,
   Trash2,
 } from 'lucide-react';
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { toast } from 'sonner';
+import DuplicateDialog from './duplicate-dialog';
+import ImportProgress from './import-progress';
+import MetadataDialog from './metadata-dialog';
 import { getNovelCover, getNovelFileExtension } from './helper';
 import { Avatar, AvatarFallback, AvatarImage } from '@/components/ui/avatar';
 import { Badge } from '@/components/ui/badge';
@@ -35,13 +41,38 @@ import {
   TableHeader,
   TableRow,
 } from '@/components/ui/table';
-import { Novel } from '@/types';
+import {
+  CustomEvent,
+  DuplicateAction,
+  ImportResult,
+  ImportStatus,
+  Novel,
+} from '@/types';
+
+const IMPORT_STATUS_MESSAGES: Record<
+  ImportStatus,
+  (title: string) => string
+> = {
+  added: () => '添加成功！',
+  skipped: (title) => `《${title}》已存在，已跳过`,
+  replaced: (title) => `已替换《${title}》的文件`,
+  duplicate: (title) => `书库中已有内容相同的《${title}》`,
+};
 
 const LibraryTab: React.FC = () => {
   const { data: novels, refresh } = useRequest(() =>
     invoke<Novel[]>('get_novel_list'),
   );
 
+  // 监视文件夹自动导入小说后刷新
+  useEffect(() => {
+    const listener = listen(CustomEvent.LibraryChange, () => refresh());
+
+    return () => {
+      listener.then((unListen) => unListen());
+    };
+  }, [refresh]);
+
   const handleDelete = (id: number) => {
     toast.promise(invoke('delete_novel', { id }), {
       loading: '删除中...',
@@ -65,22 +96,111 @@ const LibraryTab: React.FC = () => {
   };
 
   const handleAdd = async () => {
-    const file = await open({
-      multiple: false,
+    const files = await open({
+      multiple: true,
       directory: false,
-      filters: [{ name: 'novel', extensions: ['txt', 'epub'] }],
+      filters: [
+        {
+          name: 'novel',
+          extensions: [
+            'txt',
+            'epub',
+            'mobi',
+            'azw3',
+            'azw',
+            'fb2',
+            'zip',
+            'md',
+            'markdown',
+            'html',
+            'htm',
+            'pdf',
+          ],
+        },
+      ],
     });
 
-    if (!file) return;
+    if (!files?.length) return;
+
+    // 单个文件直接导入，可以处理重复的小说；多个文件在后台批量导入
+    if (files.length === 1) {
+      addNovel(files[0]);
+    } else {
+      importNovels(files, false);
+    }
+  };
+
+  // 文件夹中的每个文本文件作为一章
+  const handleAddFolder = async () => {
+    const dir = await open({
+      multiple: false,
+      directory: true,
+    });
+
+    if (!dir) return;
+
+    addNovel(dir);
+  };
+
+  // 文件夹及子文件夹中的每个文件作为一本小说
+  const handleImportFolder = async () => {
+    const dir = await open({
+      multiple: false,
+      directory: true,
+    });
+
+    if (!dir) return;
+
+    importNovels([dir], true);
+  };
+
+  const [importing, setImporting] = useState(false);
 
-    toast.promise(invoke('add_novel', { path: file }), {
+  // 批量导入时跳过重复的小说，避免逐个询问
+  const importNovels = (paths: string[], recursive: boolean) => {
+    setImporting(true);
+
+    invoke('import_novels', { paths, recursive, onDuplicate: 'skip' }).catch(
+      (e) => {
+        setImporting(false);
+        toast.error(`导入失败：${e}`);
+      },
+    );
+  };
+
+  // 发现重复时等待用户选择处理方式的导入
+  const [duplicate, setDuplicate] = useState<{
+    path: string;
+    result: ImportResult;
+  }>();
+
+  const addNovel = (path: string, onDuplicate?: DuplicateAction) => {
+    const promise = invoke<ImportResult>('add_novel', { path, onDuplicate });
+
+    toast.promise(promise, {
       loading: '添加小说中...',
-      success: '添加成功！',
-      error: '添加失败！',
+      success: (result) => IMPORT_STATUS_MESSAGES[result.status](result.title),
+      error: (e) => `添加失败：${e}`,
       finally: () => refresh(),
     });
+
+    promise.then((result) => {
+      if (result.status === 'duplicate') {
+        setDuplicate({ path, result });
+      }
+    });
+  };
+
+  const handleDuplicate = (action: DuplicateAction) => {
+    if (!duplicate) return;
+
+    setDuplicate(undefined);
+    addNovel(duplicate.path, action);
   };
 
+  // 正在编辑信息的小说
+  const [editing, setEditing] = useState<Novel>();
+
   const handleShowInFolder = (path: string) => {
     invoke('reveal_item_in_dir', { path });
   };
@@ -114,13 +234,31 @@ const LibraryTab: React.FC = () => {
             value={searchQuery}
             onChange={(e) => setSearchQuery(e.target.value)}
           />
-          <Button onClick={handleAdd}>
+          <Button variant="outline" onClick={handleAddFolder}>
+            <FolderOpen className="h-4 w-4 mr-2" />
+            导入文件夹
+          </Button>
+          <Button
+            variant="outline"
+            disabled={importing}
+            onClick={handleImportFolder}
+          >
+            <Library className="h-4 w-4 mr-2" />
+            批量导入
+          </Button>
+          <Button disabled={importing} onClick={handleAdd}>
             <Plus className="h-4 w-4 mr-2" />
             导入小说
           </Button>
         </div>
       </div>
 
+      <ImportProgress
+        importing={importing}
+        setImporting={setImporting}
+        refresh={refresh}
+      />
+
       {/* 小说表格 */}
       <div className="border rounded-lg">
         <Table>
@@ -160,6 +298,15 @@ const LibraryTab: React.FC = () => {
                             在读
                           </Badge>
                         )}
+                        {!!novel.source_missing && (
+                          <Badge
+                            variant="destructive"
+                            className="text-xs px-1 py-0"
+                            title={novel.source_path}
+                          >
+                            源文件已丢失
+                          </Badge>
+                        )}
                         <span>{novel.title}</span>
                       </div>
                       <div className="text-xs text-muted-foreground truncate">
@@ -222,6 +369,9 @@ const LibraryTab: React.FC = () => {
                       >
                         在文件夹中显示
                       </DropdownMenuItem>
+                      <DropdownMenuItem onClick={() => setEditing(novel)}>
+                        编辑信息
+                      </DropdownMenuItem>
                       <DropdownMenuSeparator />
                       <DropdownMenuItem
                         variant="destructive"
@@ -247,6 +397,18 @@ const LibraryTab: React.FC = () => {
         </div>
       )}
 
+      <MetadataDialog
+        novel={editing}
+        onClose={() => setEditing(undefined)}
+        refresh={refresh}
+      />
+
+      <DuplicateDialog
+        duplicate={duplicate?.result}
+        onSelect={handleDuplicate}
+        onCancel={() => setDuplicate(undefined)}
+      />
+
       {!novels?.length && (
         <div className="text-center py-12">
           <Book size={48} className="text-muted-foreground mx-auto mb-4" />
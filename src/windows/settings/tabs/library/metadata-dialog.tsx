@@ -0,0 +1,189 @@
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { FileText } from 'lucide-react';
+import { useEffect, useState } from 'react';
+import { toast } from 'sonner';
+import { getNovelCover } from './helper';
+import { Avatar, AvatarFallback, AvatarImage } from '@/components/ui/avatar';
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Novel, NovelMetadataUpdate } from '@/types';
+
+interface MetadataDialogProps {
+  /** 正在编辑的小说，为空时不显示 */
+  novel?: Novel;
+  onClose: () => void;
+  refresh: () => void;
+}
+
+const toMetadata = (novel?: Novel): NovelMetadataUpdate => ({
+  title: novel?.title ?? '',
+  author: novel?.author ?? '',
+  description: novel?.description ?? '',
+  series: novel?.series ?? '',
+  language: novel?.language ?? '',
+});
+
+const MetadataDialog: React.FC<MetadataDialogProps> = (props) => {
+  const { novel, onClose, refresh } = props;
+
+  const [metadata, setMetadata] = useState(toMetadata(novel));
+  const [cover, setCover] = useState(novel?.cover);
+
+  useEffect(() => {
+    setMetadata(toMetadata(novel));
+    setCover(novel?.cover);
+  }, [novel]);
+
+  const handleChange = (key: keyof NovelMetadataUpdate, value: string) => {
+    setMetadata((metadata) => ({ ...metadata, [key]: value }));
+  };
+
+  const handleSelectCover = async () => {
+    if (!novel) return;
+
+    const path = await open({
+      multiple: false,
+      directory: false,
+      filters: [
+        {
+          name: 'image',
+          extensions: ['jpg', 'jpeg', 'png', 'webp', 'gif', 'bmp'],
+        },
+      ],
+    });
+
+    if (!path) return;
+
+    setCoverPath(path);
+  };
+
+  // 设置或清除封面后重新获取小说，显示处理后的封面
+  const setCoverPath = (path: string | null) => {
+    if (!novel) return;
+
+    const promise = invoke('set_novel_cover', { id: novel.id, path })
+      .then(() => invoke<Novel>('get_novel_detail', { id: novel.id }))
+      .then((detail) => setCover(detail.cover));
+
+    toast.promise(promise, {
+      loading: '处理封面中...',
+      success: path ? '封面已更新' : '封面已清除',
+      error: (e) => `设置封面失败：${e}`,
+      finally: () => refresh(),
+    });
+  };
+
+  const handleSave = () => {
+    if (!novel) return;
+
+    toast.promise(
+      invoke('update_novel_metadata', { id: novel.id, metadata }).then(
+        onClose,
+      ),
+      {
+        loading: '保存中...',
+        success: '保存成功！',
+        error: (e) => `保存失败：${e}`,
+        finally: () => refresh(),
+      },
+    );
+  };
+
+  return (
+    <Dialog open={!!novel} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>编辑信息</DialogTitle>
+          <DialogDescription>修改书名、作者、封面等信息</DialogDescription>
+        </DialogHeader>
+
+        <div className="flex items-center gap-4">
+          <Avatar className="h-24 w-18 rounded flex-shrink-0">
+            <AvatarImage
+              src={getNovelCover(cover) || undefined}
+              alt={metadata.title}
+              className="object-cover"
+            />
+            <AvatarFallback className="rounded text-xs">
+              <FileText size={16} />
+            </AvatarFallback>
+          </Avatar>
+          <div className="flex gap-2">
+            <Button type="button" variant="outline" onClick={handleSelectCover}>
+              选择图片
+            </Button>
+            <Button
+              type="button"
+              variant="outline"
+              disabled={!cover}
+              onClick={() => setCoverPath(null)}
+            >
+              清除封面
+            </Button>
+          </div>
+        </div>
+
+        <div className="grid grid-cols-2 gap-3">
+          <div className="space-y-2 col-span-2">
+            <Label>书名</Label>
+            <Input
+              value={metadata.title}
+              onChange={(e) => handleChange('title', e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label>作者</Label>
+            <Input
+              value={metadata.author ?? ''}
+              onChange={(e) => handleChange('author', e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label>系列</Label>
+            <Input
+              value={metadata.series ?? ''}
+              onChange={(e) => handleChange('series', e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label>语言</Label>
+            <Input
+              placeholder="如 zh-CN"
+              value={metadata.language ?? ''}
+              onChange={(e) => handleChange('language', e.target.value)}
+            />
+          </div>
+          <div className="space-y-2 col-span-2">
+            <Label>简介</Label>
+            <Input
+              value={metadata.description ?? ''}
+              onChange={(e) => handleChange('description', e.target.value)}
+            />
+          </div>
+        </div>
+
+        <DialogFooter>
+          <Button
+            type="button"
+            disabled={!metadata.title.trim()}
+            onClick={handleSave}
+          >
+            保存
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
+
+export default MetadataDialog;
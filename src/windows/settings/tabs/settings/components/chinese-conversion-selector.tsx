@@ -0,0 +1,45 @@
+import { useControllableValue } from 'ahooks';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+
+export const CHINESE_CONVERSION_OPTIONS = [
+  { value: 'none', label: '不转换' },
+  { value: 'simplified', label: '转为简体' },
+  { value: 'traditional', label: '转为繁体' },
+  { value: 'taiwan', label: '转为台湾正体' },
+  { value: 'hong_kong', label: '转为香港繁体' },
+];
+
+interface ChineseConversionSelectorProps {
+  value: string;
+  onChange?: (value: string) => void;
+  defaultValue?: string;
+}
+
+const ChineseConversionSelector: React.FC<ChineseConversionSelectorProps> = (
+  props,
+) => {
+  const [value, onChange] = useControllableValue(props);
+
+  return (
+    <Select value={value} onValueChange={onChange}>
+      <SelectTrigger className="w-50">
+        <SelectValue />
+      </SelectTrigger>
+      <SelectContent>
+        {CHINESE_CONVERSION_OPTIONS.map((option) => (
+          <SelectItem key={option.value} value={option.value}>
+            {option.label}
+          </SelectItem>
+        ))}
+      </SelectContent>
+    </Select>
+  );
+};
+
+export default ChineseConversionSelector;
@@ -6,6 +6,7 @@ import { Input } from '@/components/ui/input';
 interface InputWithButtonProps {
   value: number;
   onChange: (value: number) => void;
+  disabled?: boolean;
 }
 
 const InputWithButton: React.FC<InputWithButtonProps> = (props) => {
@@ -23,12 +24,14 @@ const InputWithButton: React.FC<InputWithButtonProps> = (props) => {
         className="w-20"
         type="number"
         min={0}
+        disabled={props.disabled}
         value={inputValue}
         onChange={(e) => setInputValue(Number(e.target.value))}
       />
       <Button
         type="button"
         variant="outline"
+        disabled={props.disabled}
         onClick={() => onChange(inputValue)}
       >
         确认
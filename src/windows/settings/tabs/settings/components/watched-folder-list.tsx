@@ -0,0 +1,55 @@
+import { open } from '@tauri-apps/plugin-dialog';
+import { useControllableValue } from 'ahooks';
+import { FolderPlus, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+
+interface WatchedFolderListProps {
+  value: string[];
+  onChange: (value: string[]) => void;
+}
+
+const WatchedFolderList: React.FC<WatchedFolderListProps> = (props) => {
+  const [value, onChange] = useControllableValue<string[]>(props);
+
+  const handleAdd = async () => {
+    const dir = await open({
+      multiple: false,
+      directory: true,
+    });
+
+    if (!dir || value.includes(dir)) return;
+
+    onChange([...value, dir]);
+  };
+
+  const handleRemove = (folder: string) => {
+    onChange(value.filter((item) => item !== folder));
+  };
+
+  return (
+    <div className="space-y-2">
+      {value.map((folder) => (
+        <div
+          key={folder}
+          className="flex items-center justify-between gap-2 rounded-md border px-3 py-1"
+        >
+          <span className="w-0 flex-1 truncate text-sm">{folder}</span>
+          <Button
+            type="button"
+            variant="ghost"
+            size="sm"
+            onClick={() => handleRemove(folder)}
+          >
+            <X size={14} />
+          </Button>
+        </div>
+      ))}
+      <Button type="button" variant="outline" onClick={handleAdd}>
+        <FolderPlus className="h-4 w-4 mr-2" />
+        添加文件夹
+      </Button>
+    </div>
+  );
+};
+
+export default WatchedFolderList;
@@ -4,11 +4,16 @@ export const formSchema = z.object({
   check_update_interval: z.number(),
   auto_start: z.boolean(),
   theme: z.string(),
+  watched_folders: z.array(z.string()),
   language: z.string(),
   dock_visibility: z.boolean(),
   always_on_top: z.boolean(),
   transparent: z.boolean(),
   line_size: z.number(),
+  fit_to_window: z.boolean(),
+  chinese_conversion: z.string(),
+  paragraph_navigation: z.boolean(),
+  line_width: z.number(),
   font_size: z.number(),
   font_family: z.string(),
   line_height: z.number(),
@@ -0,0 +1,63 @@
+import { invoke } from '@tauri-apps/api/core';
+import { FolderSync } from 'lucide-react';
+import { toast } from 'sonner';
+import { SubFormProps } from './types';
+import WatchedFolderList from '../components/watched-folder-list';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import {
+  FormControl,
+  FormDescription,
+  FormField,
+  FormItem,
+  FormLabel,
+} from '@/components/ui/form';
+import { useFormWatch } from '@/hooks/use-form-watch';
+
+const LibrarySettingsForm: React.FC<SubFormProps> = (props) => {
+  const { form } = props;
+
+  useFormWatch(form, 'watched_folders', (folders) => {
+    invoke('set_watched_folders', { folders }).catch((e) =>
+      toast.error(`设置监视文件夹失败：${e}`),
+    );
+  });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <FolderSync size={20} />
+          书库设置
+        </CardTitle>
+        <CardDescription>自动导入小说</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <FormField
+          control={form.control}
+          name="watched_folders"
+          render={({ field }) => (
+            <FormItem className="space-y-2">
+              <div className="space-y-1">
+                <FormLabel>监视文件夹</FormLabel>
+                <FormDescription>
+                  文件夹及子文件夹中新增的小说将自动导入，重复的小说会被跳过
+                </FormDescription>
+              </div>
+              <FormControl>
+                <WatchedFolderList {...field} />
+              </FormControl>
+            </FormItem>
+          )}
+        />
+      </CardContent>
+    </Card>
+  );
+};
+
+export default LibrarySettingsForm;
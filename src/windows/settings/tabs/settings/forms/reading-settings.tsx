@@ -1,7 +1,7 @@
 import { invoke } from '@tauri-apps/api/core';
-import { confirm } from '@tauri-apps/plugin-dialog';
 import { BookOpen, HelpCircle } from 'lucide-react';
 import { SubFormProps } from './types';
+import ChineseConversionSelector from '../components/chinese-conversion-selector';
 import ColorPicker from '../components/color-picker';
 import FontSelector from '../components/font-selector';
 import FontWeightSelector from '../components/font-weight-selector';
@@ -22,6 +22,7 @@ import {
   FormLabel,
 } from '@/components/ui/form';
 import { Separator } from '@/components/ui/separator';
+import { Switch } from '@/components/ui/switch';
 import {
   Tooltip,
   TooltipContent,
@@ -32,20 +33,20 @@ import { useFormWatch } from '@/hooks/use-form-watch';
 const ReadingSettingsForm: React.FC<SubFormProps> = (props) => {
   const { form } = props;
 
-  useFormWatch(form, 'line_size', async (lineSize) => {
-    const confirmation = await confirm(
-      '由于每页字数修改，您在当前章节的阅读进度不会被保留，是否继续',
-      {
-        title: '温馨提示',
-        kind: 'warning',
-        okLabel: '确定',
-        cancelLabel: '取消',
-      },
-    );
-
-    if (confirmation) {
-      invoke('set_line_size', { lineSize });
-    }
+  useFormWatch(form, 'line_size', (lineSize) => {
+    invoke('set_line_size', { lineSize });
+  });
+
+  useFormWatch(form, 'fit_to_window', (fitToWindow) => {
+    invoke('set_fit_to_window', { fitToWindow });
+  });
+
+  useFormWatch(form, 'chinese_conversion', (conversion) => {
+    invoke('set_chinese_conversion', { conversion });
+  });
+
+  useFormWatch(form, 'paragraph_navigation', (paragraphNavigation) => {
+    invoke('set_paragraph_navigation', { paragraphNavigation });
   });
 
   useFormWatch(form, 'font_size', (fontSize) => {
@@ -72,6 +73,8 @@ const ReadingSettingsForm: React.FC<SubFormProps> = (props) => {
     invoke('set_line_height', { lineHeight });
   });
 
+  const fitToWindow = form.watch('fit_to_window');
+
   const [
     fontSize,
     fontFamily,
@@ -124,11 +127,65 @@ const ReadingSettingsForm: React.FC<SubFormProps> = (props) => {
                   </Tooltip>
                 </FormLabel>
                 <FormDescription>
-                  每页字数变更后，会将阅读位置重置到当前章节的开头
+                  按中文字数计算，英文等半角字符计为半个字
+                </FormDescription>
+              </div>
+              <FormControl>
+                <InputWithButton {...field} disabled={fitToWindow} />
+              </FormControl>
+            </FormItem>
+          )}
+        />
+
+        <FormField
+          control={form.control}
+          name="fit_to_window"
+          render={({ field: { value, onChange, ...rest } }) => (
+            <FormItem className="col-span-2 flex items-center justify-between">
+              <div className="space-y-2">
+                <FormLabel>按窗口宽度排版</FormLabel>
+                <FormDescription>
+                  开启后，根据字体设置与阅读器窗口宽度排版，每行正好填满窗口，每页字数不再生效
+                </FormDescription>
+              </div>
+              <FormControl>
+                <Switch checked={value} onCheckedChange={onChange} {...rest} />
+              </FormControl>
+            </FormItem>
+          )}
+        />
+
+        <FormField
+          control={form.control}
+          name="chinese_conversion"
+          render={({ field }) => (
+            <FormItem className="col-span-2 flex items-center justify-between">
+              <div className="space-y-2">
+                <FormLabel>简繁转换</FormLabel>
+                <FormDescription>
+                  按词组转换正文与章节标题，可在当前阅读中为单本小说单独设置
+                </FormDescription>
+              </div>
+              <FormControl>
+                <ChineseConversionSelector {...field} />
+              </FormControl>
+            </FormItem>
+          )}
+        />
+
+        <FormField
+          control={form.control}
+          name="paragraph_navigation"
+          render={({ field: { value, onChange, ...rest } }) => (
+            <FormItem className="col-span-2 flex items-center justify-between">
+              <div className="space-y-2">
+                <FormLabel>按段落翻页</FormLabel>
+                <FormDescription>
+                  开启后，阅读器显示整个段落，上一行、下一行按段落移动
                 </FormDescription>
               </div>
               <FormControl>
-                <InputWithButton {...field} />
+                <Switch checked={value} onCheckedChange={onChange} {...rest} />
               </FormControl>
             </FormItem>
           )}
@@ -8,6 +8,7 @@ import { useForm } from 'react-hook-form';
 import { toast } from 'sonner';
 import { z } from 'zod';
 import { formSchema } from './config';
+import LibrarySettingsForm from './forms/library-settings';
 import PreferenceSettingsForm from './forms/preference-settings';
 import ReadingSettingsForm from './forms/reading-settings';
 import ShortcutSettingsForm from './forms/shortcut-settings';
@@ -76,6 +77,8 @@ const SettingsTab: React.FC = () => {
         <PreferenceSettingsForm form={form} />
         {/* 更新设置 */}
         <UpdateSettingsForm form={form} />
+        {/* 书库设置 */}
+        <LibrarySettingsForm form={form} />
         {/* 窗口设置 */}
         <WindowSettingsForm form={form} />
         {/* 快捷键设置 */}